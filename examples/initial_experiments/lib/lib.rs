@@ -79,11 +79,13 @@ fn hello_world() {
 fn setup_callback() {
     let time = callbacks::Time::SimTime { high: 1, low: 2 };
     callbacks::CallbackBuilder::new()
+        .reason(callbacks::CallbackReason::StartOfSimulation)
         .call(start_of_simulation_callback)
-        .register();
+        .register()
+        .expect("Failed to register the start of simulation callback");
 }
 
-fn start_of_simulation_callback() {
+fn start_of_simulation_callback(_event: &callbacks::CallbackEvent) {
     sim_println!("Now we can do more stuff!");
     use std::fmt::Write as _;
     let mut printer = print::SimulatorPrinter::new();
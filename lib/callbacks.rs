@@ -1,16 +1,44 @@
 /*
  * File:    callbacks.rs
- * Brief:   TODO
+ * Brief:   Registering Rust closures as simulator callbacks (using vpi_register_cb()).
  *
  * Copyright (C) 2023 John Jekel
  * See the LICENSE file at the root of the project for licensing info.
  *
- * TODO longer description
+ * Every registration gets its own heap-allocated CallbackDataWrapper holding the s_cb_data (and the
+ * s_vpi_time and s_vpi_value it points to) along with the closure, so any number of callbacks can be
+ * registered at once. The wrapper is freed exactly once: when the callback is removed through its
+ * CallbackHandle, or right after a one-shot callback fires. If a callback removes itself while it is
+ * running, freeing is deferred until the outermost call returns.
+ *
+ * The simulator may call a callback again while its closure is still running (ex. when the closure
+ * writes a value that triggers its own cbValueChange). Those calls are skipped rather than calling the
+ * closure twice at once, which would need two mutable borrows of it.
  *
 */
 
 /*!
- * TODO rustdoc for this file here
+ * Registering Rust closures as simulator callbacks.
+ *
+ * Use a [`CallbackBuilder`] to choose the [`CallbackReason`] and, depending on the reason, the object,
+ * time and value format, then register the closure to call. The closure is given a [`CallbackEvent`]
+ * describing what happened, so one closure can serve many objects:
+ *
+ * ```ignore
+ * callbacks::CallbackBuilder::new()
+ *     .reason(callbacks::CallbackReason::ValueChange)
+ *     .object(handle)
+ *     .value_format::<LogicVec>()
+ *     .call(|event| sim_println!("{:?} is now {:?}", event.object(), event.value()))
+ *     .register()?;
+ * ```
+ *
+ * Registering returns a [`CallbackHandle`] that can remove the callback later. Dropping the handle
+ * leaves the callback registered. Time-based callbacks (ex. [`CallbackReason::AfterDelay`]) only
+ * happen once, after which they are cleaned up automatically.
+ *
+ * Callbacks may be registered during startup routines (ex. for [`CallbackReason::StartOfSimulation`]).
+ *
 */
 
 /* ------------------------------------------------------------------------------------------------
@@ -23,7 +51,15 @@
  * Uses
  * --------------------------------------------------------------------------------------------- */
 
-//TODO (includes "use ..." and "extern crate ...")
+use std::cell::Cell;
+use std::mem::ManuallyDrop;
+use std::rc::Rc;
+
+use crate::ObjectHandle;
+use crate::result::{self, Error, Result};
+use crate::startup::panic_if_not_main_thread;
+use crate::time::{self, SimTime};
+use crate::value::{GetValue, Value};
 
 /* ------------------------------------------------------------------------------------------------
  * Macros
@@ -42,27 +78,33 @@
  * --------------------------------------------------------------------------------------------- */
 
 //TODO
-//TESTING
-static mut START_OF_SIM_CALLBACK_DATA: sv_bindings::t_cb_data = sv_bindings::t_cb_data {
-    reason: sv_bindings::cbAtStartOfSimTime as i32,
-    cb_rtn: None,//Some(start_of_sim_callback),
-    obj: std::ptr::null_mut(),
-    time: std::ptr::null_mut(),
-    //time: unsafe { &mut VPI_TIME },//Doesn't work :(
-    value: std::ptr::null_mut(),
-    index: 0,
-    user_data: std::ptr::null_mut()
-};
 
 /* ------------------------------------------------------------------------------------------------
  * Types
  * --------------------------------------------------------------------------------------------- */
 
+type CallbackClosure = Box<dyn FnMut(&CallbackEvent)>;
+
 pub struct CallbackBuilder {
-    func: Option<Box<dyn FnMut()>>
+    reason: CallbackReason,
+    object: Option<ObjectHandle>,
+    time: Time,
+    value_format: i32,
+    func: Option<CallbackClosure>
 }
 
-#[derive(Clone, Copy, Debug)]
+///What happened to cause a callback, passed to its closure
+#[derive(Debug)]
+pub struct CallbackEvent {
+    reason: CallbackReason,
+    time: SimTime,
+    value: Option<Value>,
+    //The simulator owns this handle, so we must never release it
+    object: Option<ManuallyDrop<ObjectHandle>>,
+    index: i32
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Time {
     ScaledRealTime(f64),
     SimTime{high: u32, low: u32},
@@ -77,16 +119,88 @@ pub enum TimeType {
     SuppressTime = sv_bindings::vpiSuppressTime,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[repr(i32)]
 pub enum CallbackReason {
-    Todo
-    //TODO
+    //Simulation events
+    ValueChange = sv_bindings::cbValueChange,                   // the object's value changed
+    Stmt = sv_bindings::cbStmt,                                 // a statement is about to execute
+    Force = sv_bindings::cbForce,                               // a force was applied
+    Release = sv_bindings::cbRelease,                           // a force was released
+    Assign = sv_bindings::cbAssign,                             // a procedural continuous assign
+    Deassign = sv_bindings::cbDeassign,                         // a procedural continuous deassign
+    Disable = sv_bindings::cbDisable,                           // a task, function or block was disabled
+
+    //Time events
+    AtStartOfSimTime = sv_bindings::cbAtStartOfSimTime,         // the start of the given time
+    ReadWriteSynch = sv_bindings::cbReadWriteSynch,             // values can still be written this time step
+    ReadOnlySynch = sv_bindings::cbReadOnlySynch,               // values are final for this time step
+    NextSimTime = sv_bindings::cbNextSimTime,                   // the start of the next time step
+    AfterDelay = sv_bindings::cbAfterDelay,                     // the given delay from now
+    NBASynch = sv_bindings::cbNBASynch,                         // before nonblocking assignments are done
+    AtEndOfSimTime = sv_bindings::cbAtEndOfSimTime,             // the end of the given time
+
+    //Action events
+    EndOfCompile = sv_bindings::cbEndOfCompile,                 // the design has been elaborated
+    StartOfSimulation = sv_bindings::cbStartOfSimulation,       // just before time 0
+    EndOfSimulation = sv_bindings::cbEndOfSimulation,           // $finish or the end of events
+    Error = sv_bindings::cbError,                               // the simulator hit an error
+    TchkViolation = sv_bindings::cbTchkViolation,               // a timing check was violated
+    StartOfSave = sv_bindings::cbStartOfSave,                   // $save is starting
+    EndOfSave = sv_bindings::cbEndOfSave,                       // $save is done
+    StartOfRestart = sv_bindings::cbStartOfRestart,             // $restart is starting
+    EndOfRestart = sv_bindings::cbEndOfRestart,                 // $restart is done
+    StartOfReset = sv_bindings::cbStartOfReset,                 // $reset is starting
+    EndOfReset = sv_bindings::cbEndOfReset,                     // $reset is done
+    EnterInteractive = sv_bindings::cbEnterInteractive,         // entering interactive mode
+    ExitInteractive = sv_bindings::cbExitInteractive,           // leaving interactive mode
+    InteractiveScopeChange = sv_bindings::cbInteractiveScopeChange,// $scope changed the interactive scope
+    UnresolvedSystf = sv_bindings::cbUnresolvedSystf,           // an unknown system task or function was called
+    PLIError = sv_bindings::cbPLIError,                         // a VPI routine hit an error
+    Signal = sv_bindings::cbSignal,                             // the simulator received a signal
+
+    //SystemVerilog events
+    #[cfg(feature = "ieee1800-2005")] StartOfThread = sv_bindings::cbStartOfThread,   // a thread was created
+    #[cfg(feature = "ieee1800-2005")] EndOfThread = sv_bindings::cbEndOfThread,       // a thread was deleted
+    #[cfg(feature = "ieee1800-2005")] EnterThread = sv_bindings::cbEnterThread,       // a thread was resumed
+    #[cfg(feature = "ieee1800-2005")] StartOfFrame = sv_bindings::cbStartOfFrame,     // a frame started executing
+    #[cfg(feature = "ieee1800-2005")] EndOfFrame = sv_bindings::cbEndOfFrame,         // a frame finished executing
+    #[cfg(feature = "ieee1800-2005")] SizeChange = sv_bindings::cbSizeChange,         // a dynamic array, queue, etc. changed size
+    #[cfg(feature = "ieee1800-2005")] CreateObj = sv_bindings::cbCreateObj,           // a class object was created
+    #[cfg(feature = "ieee1800-2005")] ReclaimObj = sv_bindings::cbReclaimObj,         // a class object was reclaimed
+    #[cfg(feature = "ieee1800-2005")] EndOfObject = sv_bindings::cbEndOfObject        // a transient object was deleted
+}
+
+///A registered callback, which can be used to remove it
+#[derive(Debug)]
+pub struct CallbackHandle {
+    raw_handle: sv_bindings::vpiHandle,
+    wrapper: *mut CallbackDataWrapper,
+    state: Rc<CallbackState>
 }
 
 struct CallbackDataWrapper {//Self referential
     raw_cb_data: sv_bindings::t_cb_data,
-    func: Option<Box<dyn FnMut()>>
+    raw_time: sv_bindings::t_vpi_time,
+    raw_value: sv_bindings::t_vpi_value,
+    //Kept alive for as long as the callback is registered since the simulator may hold onto it
+    _object: Option<ObjectHandle>,
+    //The object's size, looked up when registering since the value can't be decoded without it and
+    //asking the simulator in the middle of a callback could clobber the value it filled in
+    size: Option<usize>,
+    func: CallbackClosure,
+    reason: CallbackReason,
+    one_shot: bool,
+    happens_once: bool,
+    raw_handle: sv_bindings::vpiHandle,//Filled in once the callback is registered
+    state: Rc<CallbackState>
+}
+
+///Shared between the wrapper and the CallbackHandle so that each knows whether the other freed it
+#[derive(Debug, Default)]
+struct CallbackState {
+    depth: Cell<u32>,//How many calls to closure_wrapper() are currently in progress (including skipped ones)
+    finished: Cell<bool>//The callback was removed or has fired for the last time
 }
 
 /* ------------------------------------------------------------------------------------------------
@@ -97,63 +211,276 @@ impl CallbackDataWrapper {
     fn make_self_referential(&mut self) {//Only call this when the wrapper has been pinned in memory
         let self_ptr: *mut CallbackDataWrapper = self;
         self.raw_cb_data.user_data = self_ptr.cast();
+        self.raw_cb_data.time = &mut self.raw_time;
+        self.raw_cb_data.value = &mut self.raw_value;
     }
 }
 
 impl CallbackBuilder {
     pub fn new() -> CallbackBuilder {
         CallbackBuilder {
+            reason: CallbackReason::StartOfSimulation,
+            object: None,
+            time: Time::SimTime{high: 0, low: 0},
+            value_format: sv_bindings::vpiSuppressVal,
             func: None
         }
     }
 
-    pub fn call(mut self, func: impl FnMut() + 'static) -> CallbackBuilder {
+    ///What the callback should be called for (defaults to [`CallbackReason::StartOfSimulation`])
+    pub fn reason(mut self, reason: CallbackReason) -> CallbackBuilder {
+        self.reason = reason;
+        self
+    }
+
+    ///The object the callback is for (ex. the signal for [`CallbackReason::ValueChange`])
+    ///
+    ///The object is kept alive until the callback is removed.
+    pub fn object(mut self, object: impl Into<ObjectHandle>) -> CallbackBuilder {
+        self.object = Some(object.into());
+        self
+    }
+
+    ///For time-based reasons, when the callback should happen. Otherwise, the format the time
+    ///should be reported in when the callback happens (defaults to `Time::SimTime{high: 0, low: 0}`).
+    pub fn time(mut self, time: Time) -> CallbackBuilder {
+        self.time = time;
+        self
+    }
+
+    ///The format the object's value should be reported in when the callback happens (by default,
+    ///it isn't reported at all)
+    pub fn value_format<T: GetValue>(mut self) -> CallbackBuilder {
+        self.value_format = T::FORMAT;
+        self
+    }
+
+    pub fn call(mut self, func: impl FnMut(&CallbackEvent) + 'static) -> CallbackBuilder {
         self.func = Some(Box::new(func));
         self
     }
 
     extern "C" fn closure_wrapper(cb_data: *mut sv_bindings::t_cb_data) -> i32 {
-        //TODO justify safety
-        unsafe {
-            //https://users.rust-lang.org/t/why-cant-a-convert-a-mut-c-void-into-mut-dyn-std-read/95780/7
-            //TODO try to make this more efficient in the future
-            let thin_ptr: *mut *mut dyn FnMut() = (*cb_data).user_data.cast();
-            let fat_ptr: *mut dyn FnMut() = *thin_ptr;
-            (*fat_ptr)();//TODO pass the closure extra info about what happened
-            //No need to re-box things since the closure may be re-called multiple times and we
-            //don't want to drop it
-            //FIXME how should we clean this up at the end?
-            //FIXME what if this is called from multiple threads?
-        };
+        //SAFETY: The simulator gives us back the user_data we registered the callback with, which
+        //is a pointer to the CallbackDataWrapper we leaked in register(). It is only freed once
+        //the callback is finished and the outermost call has returned, after which the simulator
+        //won't call us again. Callbacks only ever happen on the main thread, and the only mutable
+        //borrow (of the closure) is never taken by nested calls.
+        let wrapper: *mut CallbackDataWrapper = unsafe { (*cb_data).user_data.cast() };
+        let state = unsafe { (*wrapper).state.clone() };
+
+        //Marked before calling the closure so that a one-shot callback removing itself does nothing,
+        //since the simulator removes it on its own once we return
+        if unsafe { (*wrapper).one_shot } {
+            state.finished.set(true);
+        }
+
+        let depth = state.depth.get();
+        state.depth.set(depth + 1);
+        if depth == 0 {//Re-entrant calls are skipped since the closure is already borrowed
+            //SAFETY: The simulator gives us valid callback data for the duration of the callback
+            let event = unsafe { CallbackEvent::from_raw(&*cb_data, (*wrapper).reason, (*wrapper).size) };
+            unsafe { ((*wrapper).func)(&event) };
+        }
+        state.depth.set(depth);
+
+        //The wrapper is still in use by the calls we're nested in, so only the outermost one cleans up
+        if depth > 0 {
+            return 0;
+        }
+
+        //Nothing else would ever free these, even though they can't happen again
+        if unsafe { (*wrapper).happens_once } && !state.finished.get() {
+            //SAFETY: We're in a callback on the main thread, the callback isn't finished so the
+            //simulator hasn't freed its handle yet, and callbacks may remove themselves
+            unsafe { sv_bindings::vpi_remove_cb((*wrapper).raw_handle) };
+            state.finished.set(true);
+        }
+
+        //If the closure removed its own callback, CallbackHandle::remove() left freeing it to us
+        if state.finished.get() {
+            //SAFETY: The wrapper was leaked from a box in register() and nothing will use it again
+            drop(unsafe { Box::from_raw(wrapper) });
+        }
 
         0
     }
 
-    pub fn register(mut self) {
-        //TESTING
-        unsafe {
-            START_OF_SIM_CALLBACK_DATA.cb_rtn = Some(CallbackBuilder::closure_wrapper);//Some(self.func.unwrap());
-            let time = Time::SimTime{high: 1, low: 2};
-            let ctime: sv_bindings::t_vpi_time = time.into();
-            let ctimebox = Box::new(ctime);
-            START_OF_SIM_CALLBACK_DATA.time = Box::into_raw(ctimebox);
-
-            assert!(self.func.is_some());
-            let boxed_closure = self.func.take().unwrap();
-            //https://users.rust-lang.org/t/why-cant-a-convert-a-mut-c-void-into-mut-dyn-std-read/95780/7
-            //TODO try to make this more efficient in the future
-            let fat_ptr: *mut dyn FnMut() = Box::into_raw(boxed_closure);
-            let boxed_fat_ptr = Box::new(fat_ptr);
-            let thin_ptr: *mut *mut dyn FnMut() = Box::into_raw(boxed_fat_ptr);
-            START_OF_SIM_CALLBACK_DATA.user_data = thin_ptr.cast();
-
-            //TODO in the wrapper around the registration callback panic if SupressTime or Time is NULL
-            sv_bindings::vpi_register_cb(
-                &mut START_OF_SIM_CALLBACK_DATA
-            );
-        
+    ///Registers the callback with the simulator (using `vpi_register_cb()`)
+    ///
+    ///Panics if no closure was provided with [`CallbackBuilder::call()`].
+    pub fn register(self) -> Result<CallbackHandle> {
+        panic_if_not_main_thread!();
+
+        let func = self.func.expect("A closure must be provided with call() before registering a callback");
+        let raw_object = self.object.as_ref().map_or(std::ptr::null_mut(), |object| object.handle.as_ptr());
+
+        //Only looked up when a value will be reported, since startup routines (which can't call
+        //vpi_get()) only register callbacks without one
+        let size = match &self.object {
+            Some(object) if self.value_format != sv_bindings::vpiSuppressVal => object.size().ok(),
+            _ => None
+        };
+
+        let mut wrapper = Box::new(CallbackDataWrapper {
+            raw_cb_data: sv_bindings::t_cb_data {
+                reason: self.reason as i32,
+                cb_rtn: Some(CallbackBuilder::closure_wrapper),
+                obj: raw_object,
+                time: std::ptr::null_mut(),
+                value: std::ptr::null_mut(),
+                index: 0,
+                user_data: std::ptr::null_mut()
+            },
+            raw_time: self.time.into(),
+            raw_value: sv_bindings::t_vpi_value {
+                format: self.value_format,
+                value: sv_bindings::t_vpi_value__bindgen_ty_1 { integer: 0 }
+            },
+            _object: self.object,
+            size,
+            func,
+            reason: self.reason,
+            one_shot: self.reason.is_one_shot(),
+            happens_once: self.reason.happens_once(),
+            raw_handle: std::ptr::null_mut(),
+            state: Rc::new(CallbackState::default())
+        });
+        wrapper.make_self_referential();//The box won't move the wrapper anymore
+
+        //SAFETY: We're calling vpi_register_cb() from the main thread, and the s_cb_data along with
+        //everything it points to lives in the wrapper, which we leak below so it stays valid for as
+        //long as the callback could be called
+        let raw_callback_handle = unsafe { sv_bindings::vpi_register_cb(&mut wrapper.raw_cb_data) };
+
+        if raw_callback_handle.is_null() {
+            return Err(result::last_vpi_error().unwrap_or(Box::new(Error::CallbackNotRegistered(self.reason))));
+        }
+
+        wrapper.raw_handle = raw_callback_handle;
+        let state = wrapper.state.clone();
+        Ok(CallbackHandle {
+            raw_handle: raw_callback_handle,
+            wrapper: Box::into_raw(wrapper),
+            state
+        })
+    }
+}
+
+impl CallbackHandle {
+    ///Removes the callback so it is never called again (using `vpi_remove_cb()`), freeing the closure
+    ///
+    ///Does nothing if the callback already happened for the last time. This may be called from
+    ///within the callback's own closure.
+    pub fn remove(self) -> Result<()> {
+        panic_if_not_main_thread!();
+
+        if self.state.finished.get() {
+            return Ok(());
+        }
+
+        //SAFETY: We're calling vpi_remove_cb() from the main thread, and since the callback isn't
+        //finished, the simulator hasn't freed the callback handle yet
+        if unsafe { sv_bindings::vpi_remove_cb(self.raw_handle) } != 1 {
+            return Err(result::last_vpi_error().unwrap_or(Box::new(Error::CallbackNotRemoved)));
+        }
+        self.state.finished.set(true);
+
+        //If the closure is running, the outermost closure_wrapper() call will free the wrapper once it returns
+        if self.state.depth.get() == 0 {
+            //SAFETY: The wrapper was leaked from a box in register(), and now that the callback is
+            //removed the simulator won't give it back to closure_wrapper()
+            drop(unsafe { Box::from_raw(self.wrapper) });
+        }
+
+        Ok(())
+    }
+
+    ///False once the callback was removed or has happened for the last time
+    pub fn is_registered(&self) -> bool {
+        !self.state.finished.get()
+    }
+}
+
+impl CallbackEvent {
+    ///Copies what we need out of the simulator's callback data
+    ///
+    ///# Safety
+    ///
+    ///`raw_cb_data` must be what the simulator passed to the callback, and the event must not be
+    ///used after the callback returns (since the object handle belongs to the simulator). `size` is
+    ///the size of the object the callback was registered for, if any.
+    unsafe fn from_raw(raw_cb_data: &sv_bindings::t_cb_data, reason: CallbackReason, size: Option<usize>) -> CallbackEvent {
+        let object = ObjectHandle::from_raw(raw_cb_data.obj).map(ManuallyDrop::new);
+
+        //SAFETY: The value is either null or was just filled in by the simulator in the format we
+        //asked for (or vpiSuppressVal). It's copied out before making any other VPI calls.
+        let value = unsafe { raw_cb_data.value.as_ref() }.and_then(|raw_value| {
+            if raw_value.format == sv_bindings::vpiSuppressVal {
+                return None;
+            }
+            unsafe { Value::from_raw_value(raw_value, size) }.ok()
+        });
+
+        //SAFETY: The time is either null or points to a valid s_vpi_time in the format we asked for
+        let time = match unsafe { raw_cb_data.time.as_ref() } {
+            Some(raw_time) if raw_time.type_ == sv_bindings::vpiSimTime => SimTime::from_high_low(raw_time.high, raw_time.low),
+            _ => time::sim_time()//Scaled or suppressed, so we have to ask
+        };
+
+        CallbackEvent {
+            reason,
+            time,
+            value,
+            object,
+            index: raw_cb_data.index
         }
     }
+
+    pub fn reason(&self) -> CallbackReason {
+        self.reason
+    }
+
+    ///The simulation time the callback happened at
+    pub fn time(&self) -> SimTime {
+        self.time
+    }
+
+    ///The object's new value, in the format chosen with [`CallbackBuilder::value_format()`]
+    ///
+    ///None if no format was chosen or the reason doesn't come with a value.
+    pub fn value(&self) -> Option<&Value> {
+        self.value.as_ref()
+    }
+
+    ///The object the callback happened for, if there is one
+    ///
+    ///This is only valid for the duration of the callback.
+    pub fn object(&self) -> Option<&ObjectHandle> {
+        self.object.as_deref()
+    }
+
+    ///The index of the element of the object that changed (ex. for a memory word), if applicable
+    pub fn index(&self) -> i32 {
+        self.index
+    }
+}
+
+impl CallbackReason {
+    ///Callbacks for these reasons only happen once, after which the simulator removes them itself
+    fn is_one_shot(self) -> bool {
+        matches!(self,
+            CallbackReason::AtStartOfSimTime | CallbackReason::ReadWriteSynch | CallbackReason::ReadOnlySynch |
+            CallbackReason::NextSimTime | CallbackReason::AfterDelay | CallbackReason::NBASynch |
+            CallbackReason::AtEndOfSimTime
+        )
+    }
+
+    ///Callbacks for these reasons can only happen once per simulation, but the simulator leaves
+    ///removing them to us
+    fn happens_once(self) -> bool {
+        matches!(self, CallbackReason::EndOfCompile | CallbackReason::StartOfSimulation | CallbackReason::EndOfSimulation)
+    }
 }
 
 /* ------------------------------------------------------------------------------------------------
@@ -166,6 +493,16 @@ impl CallbackBuilder {
  * Trait Implementations
  * --------------------------------------------------------------------------------------------- */
 
+impl From<sv_bindings::t_vpi_time> for Time {
+    fn from(raw_time: sv_bindings::t_vpi_time) -> Time {
+        match raw_time.type_ {
+            sv_bindings::vpiScaledRealTime  => Time::ScaledRealTime(raw_time.real),
+            sv_bindings::vpiSimTime         => Time::SimTime{high: raw_time.high, low: raw_time.low},
+            _                               => Time::SuppressTime
+        }
+    }
+}
+
 impl From<Time> for sv_bindings::t_vpi_time {
     fn from(time: Time) -> sv_bindings::t_vpi_time {
         match time {
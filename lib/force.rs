@@ -0,0 +1,170 @@
+/*
+ * File:    force.rs
+ * Brief:   Forcing and releasing nets and variables, keeping track of what is currently forced.
+ *
+ * Copyright (C) 2023 John Jekel
+ * See the LICENSE file at the root of the project for licensing info.
+ *
+ * Forcing an object hands its handle over to the list of forced objects, since handles can't be
+ * duplicated (and names don't round-trip for every object) but release_all() needs one for each.
+ * Handles are compared with vpi_compare_objects(), so any handle to the object can release it.
+ *
+*/
+
+/*!
+ * Forcing and releasing nets and variables.
+ *
+ * [`Net::force()`] and [`Variable::force()`] hold an object at a value until [`Net::release()`] or
+ * [`Variable::release()`] is called, just like `force` and `release` in SystemVerilog. Every object
+ * forced this way is remembered until it is released, so [`forced_objects()`] can be used to audit
+ * what is still forced and [`release_all()`] to clean up at the end of a test.
+ *
+ * Forcing takes the handle, since it is kept until the object is released. Any other handle to the
+ * same object (ex. from [`ObjectHandle::by_name()`]) can be used to release it early.
+ *
+ * Forces applied some other way (from SystemVerilog, or with [`PutMode::Force`]) aren't tracked.
+ *
+*/
+
+/* ------------------------------------------------------------------------------------------------
+ * Uses
+ * --------------------------------------------------------------------------------------------- */
+
+use std::cell::RefCell;
+
+use crate::ObjectHandle;
+use crate::objects::{Net, Variable};
+use crate::result::Result;
+use crate::value::{PutMode, PutValue};
+
+/* ------------------------------------------------------------------------------------------------
+ * Static Variables
+ * --------------------------------------------------------------------------------------------- */
+
+thread_local! {
+    //The objects forced with force(), in the order they were forced. Handles only exist on the main
+    //thread, so this is only ever used from there.
+    static FORCED_OBJECTS: RefCell<Vec<ObjectHandle>> = const { RefCell::new(Vec::new()) };
+}
+
+/* ------------------------------------------------------------------------------------------------
+ * Associated Functions and Methods
+ * --------------------------------------------------------------------------------------------- */
+
+impl Net {
+    ///Forces the net to the value until it is released
+    pub fn force(self, value: impl PutValue) -> Result<()> {
+        force(self.into_handle(), value)
+    }
+
+    ///Releases a force on the net, letting its drivers determine its value again
+    pub fn release(&self) -> Result<()> {
+        release(self.as_handle())
+    }
+
+    ///True if the net was forced with [`Net::force()`] and hasn't been released since
+    pub fn is_forced(&self) -> bool {
+        is_forced(self.as_handle())
+    }
+}
+
+impl Variable {
+    ///Forces the variable to the value until it is released
+    pub fn force(self, value: impl PutValue) -> Result<()> {
+        force(self.into_handle(), value)
+    }
+
+    ///Releases a force on the variable; it keeps the forced value until it is next assigned
+    pub fn release(&self) -> Result<()> {
+        release(self.as_handle())
+    }
+
+    ///True if the variable was forced with [`Variable::force()`] and hasn't been released since
+    pub fn is_forced(&self) -> bool {
+        is_forced(self.as_handle())
+    }
+}
+
+/* ------------------------------------------------------------------------------------------------
+ * Functions
+ * --------------------------------------------------------------------------------------------- */
+
+///The full names of every object that is currently forced, in the order they were forced
+///
+///Objects without a full name aren't listed, but are still released by [`release_all()`].
+pub fn forced_objects() -> Vec<String> {
+    FORCED_OBJECTS.with_borrow(|forced_objects| {
+        forced_objects.iter().filter_map(|handle| handle.full_name().ok()).collect()
+    })
+}
+
+///Releases every object that is currently forced
+///
+///Every object is attempted even if some fail; the first error is returned and the objects that
+///failed stay forced.
+pub fn release_all() -> Result<()> {
+    let forced_objects = FORCED_OBJECTS.take();
+
+    let mut first_error = None;
+    let mut still_forced = Vec::new();
+    for handle in forced_objects {
+        match handle.put_value(0, PutMode::Release) {//The value is ignored when releasing
+            Ok(_)       => {},
+            Err(error)  => {
+                first_error.get_or_insert(error);
+                still_forced.push(handle);
+            }
+        }
+    }
+
+    //Objects forced again while we were releasing (there shouldn't be any) come after the ones that failed
+    FORCED_OBJECTS.with_borrow_mut(|forced_objects| {
+        still_forced.append(forced_objects);
+        *forced_objects = still_forced;
+    });
+
+    match first_error {
+        Some(error) => Err(error),
+        None        => Ok(())
+    }
+}
+
+fn force(handle: ObjectHandle, value: impl PutValue) -> Result<()> {
+    handle.put_value(value, PutMode::Force)?;
+
+    FORCED_OBJECTS.with_borrow_mut(|forced_objects| {
+        //Forcing an object again keeps its original place (and drops the new handle)
+        if !forced_objects.contains(&handle) {
+            forced_objects.push(handle);
+        }
+    });
+    Ok(())
+}
+
+fn release(handle: &ObjectHandle) -> Result<()> {
+    //The value is ignored when releasing, so it doesn't matter what we pass
+    handle.put_value(0, PutMode::Release)?;
+
+    //Taken out first so the forced handle is dropped (releasing it) without the list borrowed
+    let released = FORCED_OBJECTS.with_borrow_mut(|forced_objects| {
+        forced_objects.iter().position(|forced| forced == handle).map(|index| forced_objects.remove(index))
+    });
+    drop(released);
+    Ok(())
+}
+
+fn is_forced(handle: &ObjectHandle) -> bool {
+    FORCED_OBJECTS.with_borrow(|forced_objects| forced_objects.contains(handle))
+}
+
+/* ------------------------------------------------------------------------------------------------
+ * Tests
+ * --------------------------------------------------------------------------------------------- */
+
+//TODO
+
+/* ------------------------------------------------------------------------------------------------
+ * Benchmarks
+ * --------------------------------------------------------------------------------------------- */
+
+//TODO
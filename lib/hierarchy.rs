@@ -0,0 +1,150 @@
+/*
+ * File:    hierarchy.rs
+ * Brief:   Walks the design hierarchy, calling a user-provided Visitor along the way.
+ *
+ * Copyright (C) 2023 John Jekel
+ * See the LICENSE file at the root of the project for licensing info.
+ *
+ * Descends through modules, interfaces, programs, generate scopes and named blocks either depth-first
+ * or breadth-first.
+ *
+*/
+
+/*!
+ * Walks the design hierarchy, calling a user-provided [`Visitor`] along the way.
+ *
+ * Implement [`Visitor`] (all of its methods have empty default implementations, so only override what
+ * you need) and pass it to [`walk()`] to visit the whole design, or to [`walk_from()`] to start at a
+ * particular scope.
+ *
+ * The walker descends through modules, interfaces, programs, generate scopes and named blocks. Tasks
+ * and functions are not descended into.
+ *
+*/
+
+/* ------------------------------------------------------------------------------------------------
+ * Uses
+ * --------------------------------------------------------------------------------------------- */
+
+use std::collections::VecDeque;
+
+use crate::{ObjectIterator, ObjectType};
+use crate::objects::{Net, Scope, Variable};
+
+/* ------------------------------------------------------------------------------------------------
+ * Types
+ * --------------------------------------------------------------------------------------------- */
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Order {
+    ///Each scope's internal scopes are walked before moving on to its siblings
+    DepthFirst,
+    ///Every scope at one level of the hierarchy is walked before moving on to the next level
+    BreadthFirst
+}
+
+/* ------------------------------------------------------------------------------------------------
+ * Traits And Default Implementations
+ * --------------------------------------------------------------------------------------------- */
+
+///Called by [`walk()`] and [`walk_from()`] for each part of the design hierarchy
+///
+///For every scope, `enter_scope()` is called first, then `visit_net()` and `visit_variable()` for each
+///net and variable directly inside it, and finally `leave_scope()`. When walking depth-first,
+///`leave_scope()` is called only after all of the scope's internal scopes have been walked too, so
+///calls to `enter_scope()` and `leave_scope()` nest like the hierarchy itself does.
+pub trait Visitor {
+    ///Return false to skip the scope entirely (`leave_scope()` won't be called for it either)
+    fn enter_scope(&mut self, _scope: &Scope) -> bool {
+        true
+    }
+
+    ///Takes ownership of the net so the visitor can hold on to it (ex. to collect matching signals)
+    fn visit_net(&mut self, _net: Net) {}
+
+    ///Takes ownership of the variable so the visitor can hold on to it
+    fn visit_variable(&mut self, _variable: Variable) {}
+
+    fn leave_scope(&mut self, _scope: &Scope) {}
+}
+
+/* ------------------------------------------------------------------------------------------------
+ * Functions
+ * --------------------------------------------------------------------------------------------- */
+
+///Walks the entire design, starting from every top-level module (and program and interface, with
+///the IEEE 1800 features)
+pub fn walk(visitor: &mut impl Visitor, order: Order) {
+    let top_level_types = [
+        ObjectType::Module,
+        #[cfg(feature = "ieee1800-2005")] ObjectType::Program,
+        #[cfg(feature = "ieee1800-2005")] ObjectType::Interface,
+    ];
+
+    let top_level_scopes = top_level_types.into_iter()
+        .flat_map(ObjectIterator::new)
+        .filter_map(|handle| Scope::try_from(handle).ok());
+    walk_scopes(top_level_scopes.collect(), visitor, order);
+}
+
+///Walks the part of the design hierarchy under (and including) the root
+pub fn walk_from(root: Scope, visitor: &mut impl Visitor, order: Order) {
+    walk_scopes(vec![root], visitor, order);
+}
+
+fn walk_scopes(roots: Vec<Scope>, visitor: &mut impl Visitor, order: Order) {
+    match order {
+        Order::DepthFirst => {
+            for root in roots {
+                walk_depth_first(&root, visitor);
+            }
+        },
+        Order::BreadthFirst => {
+            let mut queue: VecDeque<Scope> = roots.into();
+            while let Some(scope) = queue.pop_front() {
+                if visit_scope_contents(&scope, visitor) {
+                    queue.extend(scope.internal_scopes());
+                    visitor.leave_scope(&scope);
+                }
+            }
+        }
+    }
+}
+
+fn walk_depth_first(scope: &Scope, visitor: &mut impl Visitor) {
+    if visit_scope_contents(scope, visitor) {
+        for internal_scope in scope.internal_scopes() {
+            walk_depth_first(&internal_scope, visitor);
+        }
+        visitor.leave_scope(scope);
+    }
+}
+
+///Enters the scope and visits its nets and variables, returning false if the visitor skipped it
+fn visit_scope_contents(scope: &Scope, visitor: &mut impl Visitor) -> bool {
+    if !visitor.enter_scope(scope) {
+        return false;
+    }
+
+    for net in scope.nets() {
+        visitor.visit_net(net);
+    }
+
+    for variable in scope.variables() {
+        visitor.visit_variable(variable);
+    }
+
+    true
+}
+
+/* ------------------------------------------------------------------------------------------------
+ * Tests
+ * --------------------------------------------------------------------------------------------- */
+
+//TODO
+
+/* ------------------------------------------------------------------------------------------------
+ * Benchmarks
+ * --------------------------------------------------------------------------------------------- */
+
+//TODO
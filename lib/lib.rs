@@ -18,22 +18,100 @@
  * --------------------------------------------------------------------------------------------- */
 
 pub mod callbacks;
+pub mod force;
+pub mod hierarchy;
 pub mod info;
+pub mod logic;
+pub mod monitor;
+pub mod objects;
 pub mod result;
+pub mod search;
 pub mod startup;
+pub mod time;
+pub mod timer;
+pub mod value;
 pub mod print;
 
 /* ------------------------------------------------------------------------------------------------
  * Uses
  * --------------------------------------------------------------------------------------------- */
 
-//TODO (includes "use ..." and "extern crate ...")
+use crate::result::{Error, Result};
+use crate::startup::panic_if_in_startup_routine;
+use crate::startup::panic_if_not_main_thread;
+
+use std::fmt;
+use std::fmt::Display;
+use std::str::FromStr;
 
 /* ------------------------------------------------------------------------------------------------
  * Macros
  * --------------------------------------------------------------------------------------------- */
 
-//TODO
+///Defines ObjectType along with conversions to and from the raw values the simulator uses
+macro_rules! object_types {
+    ($($(#[$attr:meta])* $variant:ident = $raw:path,)*) => {
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        #[non_exhaustive]
+        pub enum ObjectType {
+            $($(#[$attr])* $variant,)*
+            Unknown(i32)//Vendor-specific, or from a standard whose feature isn't enabled
+        }
+
+        impl From<ObjectType> for i32 {
+            fn from(object_type: ObjectType) -> i32 {
+                match object_type {
+                    $($(#[$attr])* ObjectType::$variant => $raw,)*
+                    ObjectType::Unknown(raw) => raw
+                }
+            }
+        }
+
+        impl TryFrom<i32> for ObjectType {
+            type Error = Box<Error>;
+
+            fn try_from(raw: i32) -> Result<ObjectType> {
+                match raw {
+                    $($(#[$attr])* $raw => Ok(ObjectType::$variant),)*
+                    raw if raw > 0 => Ok(ObjectType::Unknown(raw)),
+                    _ => Err(Box::new(Error::InvalidObjectType(raw)))//Ex. vpiUndefined
+                }
+            }
+        }
+
+        ///Uses the same names as the standard (ex. "vpiModule"), or the raw value if Unknown
+        impl Display for ObjectType {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match self {
+                    $($(#[$attr])* ObjectType::$variant => write!(f, concat!("vpi", stringify!($variant))),)*
+                    ObjectType::Unknown(raw) => write!(f, "{}", raw)
+                }
+            }
+        }
+
+        ///Accepts names with or without the "vpi" prefix (ex. "vpiModule" or "Module"), as well as
+        ///raw values (ex. "32")
+        impl FromStr for ObjectType {
+            type Err = Box<Error>;
+
+            fn from_str(string: &str) -> Result<ObjectType> {
+                let name = string.strip_prefix("vpi").unwrap_or(string);
+                match name {
+                    $($(#[$attr])* stringify!($variant) => Ok(ObjectType::$variant),)*
+                    _ => {
+                        if let Some(object_type) = ObjectType::from_alias(name) {
+                            Ok(object_type)
+                        } else if let Ok(raw) = string.parse::<i32>() {
+                            raw.try_into()
+                        } else {
+                            Err(Box::new(Error::InvalidObjectTypeName(string.to_string())))
+                        }
+                    }
+                }
+            }
+        }
+    };
+}
 
 /* ------------------------------------------------------------------------------------------------
  * Constants
@@ -62,9 +140,7 @@ pub struct ObjectIterator {
     iterator_handle: Option<ObjectHandle>
 }
 
-#[derive(Clone, Copy, Debug)]
-#[repr(i32)]
-pub enum ObjectType {
+object_types! {
     Always = sv_bindings::vpiAlways,               // always procedure
     AssignStmt = sv_bindings::vpiAssignStmt,       // quasi-continuous assignment
     Assignment = sv_bindings::vpiAssignment,       // procedural assignment
@@ -159,42 +235,511 @@ pub enum ObjectType {
     GenScopeArray = sv_bindings::vpiGenScopeArray,          // array of generated scopes
     GenScope = sv_bindings::vpiGenScope,                    // A generated scope
     GenVar = sv_bindings::vpiGenVar,                        // Object used to instantiate gen scopes
+
+    // Object types added with 1800-2005
+    #[cfg(feature = "ieee1800-2005")] Package = sv_bindings::vpiPackage,                          // package
+    #[cfg(feature = "ieee1800-2005")] Interface = sv_bindings::vpiInterface,                      // interface instance
+    #[cfg(feature = "ieee1800-2005")] Program = sv_bindings::vpiProgram,                          // program instance
+    #[cfg(feature = "ieee1800-2005")] InterfaceArray = sv_bindings::vpiInterfaceArray,            // interface instance array
+    #[cfg(feature = "ieee1800-2005")] ProgramArray = sv_bindings::vpiProgramArray,                // program instance array
+    #[cfg(feature = "ieee1800-2005")] Typespec = sv_bindings::vpiTypespec,                        // type specification
+    #[cfg(feature = "ieee1800-2005")] Modport = sv_bindings::vpiModport,                          // interface modport
+    #[cfg(feature = "ieee1800-2005")] InterfaceTfDecl = sv_bindings::vpiInterfaceTfDecl,          // interface task/function declaration
+    #[cfg(feature = "ieee1800-2005")] RefObj = sv_bindings::vpiRefObj,                            // reference to another object
+    #[cfg(feature = "ieee1800-2005")] TypeParameter = sv_bindings::vpiTypeParameter,              // type parameter
+    #[cfg(feature = "ieee1800-2005")] LongIntVar = sv_bindings::vpiLongIntVar,                    // longint variable
+    #[cfg(feature = "ieee1800-2005")] ShortIntVar = sv_bindings::vpiShortIntVar,                  // shortint variable
+    #[cfg(feature = "ieee1800-2005")] IntVar = sv_bindings::vpiIntVar,                            // int variable
+    #[cfg(feature = "ieee1800-2005")] ShortRealVar = sv_bindings::vpiShortRealVar,                // shortreal variable
+    #[cfg(feature = "ieee1800-2005")] ByteVar = sv_bindings::vpiByteVar,                          // byte variable
+    #[cfg(feature = "ieee1800-2005")] ClassVar = sv_bindings::vpiClassVar,                        // class variable
+    #[cfg(feature = "ieee1800-2005")] StringVar = sv_bindings::vpiStringVar,                      // string variable
+    #[cfg(feature = "ieee1800-2005")] EnumVar = sv_bindings::vpiEnumVar,                          // enum variable
+    #[cfg(feature = "ieee1800-2005")] StructVar = sv_bindings::vpiStructVar,                      // struct variable
+    #[cfg(feature = "ieee1800-2005")] UnionVar = sv_bindings::vpiUnionVar,                        // union variable
+    #[cfg(feature = "ieee1800-2005")] BitVar = sv_bindings::vpiBitVar,                            // bit variable
+    #[cfg(feature = "ieee1800-2005")] ClassObj = sv_bindings::vpiClassObj,                        // class object
+    #[cfg(feature = "ieee1800-2005")] ChandleVar = sv_bindings::vpiChandleVar,                    // chandle variable
+    #[cfg(feature = "ieee1800-2005")] LongIntTypespec = sv_bindings::vpiLongIntTypespec,          // longint typespec
+    #[cfg(feature = "ieee1800-2005")] ShortRealTypespec = sv_bindings::vpiShortRealTypespec,      // shortreal typespec
+    #[cfg(feature = "ieee1800-2005")] ByteTypespec = sv_bindings::vpiByteTypespec,                // byte typespec
+    #[cfg(feature = "ieee1800-2005")] ShortIntTypespec = sv_bindings::vpiShortIntTypespec,        // shortint typespec
+    #[cfg(feature = "ieee1800-2005")] IntTypespec = sv_bindings::vpiIntTypespec,                  // int typespec
+    #[cfg(feature = "ieee1800-2005")] ClassTypespec = sv_bindings::vpiClassTypespec,              // class typespec
+    #[cfg(feature = "ieee1800-2005")] StringTypespec = sv_bindings::vpiStringTypespec,            // string typespec
+    #[cfg(feature = "ieee1800-2005")] ChandleTypespec = sv_bindings::vpiChandleTypespec,          // chandle typespec
+    #[cfg(feature = "ieee1800-2005")] EnumTypespec = sv_bindings::vpiEnumTypespec,                // enum typespec
+    #[cfg(feature = "ieee1800-2005")] EnumConst = sv_bindings::vpiEnumConst,                      // enum constant
+    #[cfg(feature = "ieee1800-2005")] IntegerTypespec = sv_bindings::vpiIntegerTypespec,          // integer typespec
+    #[cfg(feature = "ieee1800-2005")] TimeTypespec = sv_bindings::vpiTimeTypespec,                // time typespec
+    #[cfg(feature = "ieee1800-2005")] RealTypespec = sv_bindings::vpiRealTypespec,                // real typespec
+    #[cfg(feature = "ieee1800-2005")] StructTypespec = sv_bindings::vpiStructTypespec,            // struct typespec
+    #[cfg(feature = "ieee1800-2005")] UnionTypespec = sv_bindings::vpiUnionTypespec,              // union typespec
+    #[cfg(feature = "ieee1800-2005")] BitTypespec = sv_bindings::vpiBitTypespec,                  // bit typespec
+    #[cfg(feature = "ieee1800-2005")] LogicTypespec = sv_bindings::vpiLogicTypespec,              // logic typespec
+    #[cfg(feature = "ieee1800-2005")] ArrayTypespec = sv_bindings::vpiArrayTypespec,              // array typespec
+    #[cfg(feature = "ieee1800-2005")] VoidTypespec = sv_bindings::vpiVoidTypespec,                // void typespec
+    #[cfg(feature = "ieee1800-2005")] TypespecMember = sv_bindings::vpiTypespecMember,            // member of a struct/union typespec
+    #[cfg(feature = "ieee1800-2005")] DistItem = sv_bindings::vpiDistItem,                        // dist item in a constraint
+    #[cfg(feature = "ieee1800-2005")] AliasStmt = sv_bindings::vpiAliasStmt,                      // net alias statement
+    #[cfg(feature = "ieee1800-2005")] Thread = sv_bindings::vpiThread,                            // thread of execution
+    #[cfg(feature = "ieee1800-2005")] MethodFuncCall = sv_bindings::vpiMethodFuncCall,            // class method function call
+    #[cfg(feature = "ieee1800-2005")] MethodTaskCall = sv_bindings::vpiMethodTaskCall,            // class method task call
+    #[cfg(feature = "ieee1800-2005")] ClockingBlock = sv_bindings::vpiClockingBlock,              // clocking block
+    #[cfg(feature = "ieee1800-2005")] ClockingIODecl = sv_bindings::vpiClockingIODecl,            // clocking block input/output declaration
+    #[cfg(feature = "ieee1800-2005")] ClassDefn = sv_bindings::vpiClassDefn,                      // class definition
+    #[cfg(feature = "ieee1800-2005")] Constraint = sv_bindings::vpiConstraint,                    // constraint block
+    #[cfg(feature = "ieee1800-2005")] ConstraintOrdering = sv_bindings::vpiConstraintOrdering,    // solve-before constraint
+    #[cfg(feature = "ieee1800-2005")] PropertyDecl = sv_bindings::vpiPropertyDecl,                // property declaration
+    #[cfg(feature = "ieee1800-2005")] PropertySpec = sv_bindings::vpiPropertySpec,                // property specification
+    #[cfg(feature = "ieee1800-2005")] PropertyExpr = sv_bindings::vpiPropertyExpr,                // property expression
+    #[cfg(feature = "ieee1800-2005")] MulticlockSequenceExpr = sv_bindings::vpiMulticlockSequenceExpr, // multiclock sequence expression
+    #[cfg(feature = "ieee1800-2005")] ClockedSeq = sv_bindings::vpiClockedSeq,                    // clocked sequence
+    #[cfg(feature = "ieee1800-2005")] PropertyInst = sv_bindings::vpiPropertyInst,                // property instance
+    #[cfg(feature = "ieee1800-2005")] SequenceDecl = sv_bindings::vpiSequenceDecl,                // sequence declaration
+    #[cfg(feature = "ieee1800-2005")] CaseProperty = sv_bindings::vpiCaseProperty,                // property case
+    #[cfg(feature = "ieee1800-2005")] SequenceInst = sv_bindings::vpiSequenceInst,                // sequence instance
+    #[cfg(feature = "ieee1800-2005")] ImmediateAssert = sv_bindings::vpiImmediateAssert,          // immediate assertion
+    #[cfg(feature = "ieee1800-2005")] Return = sv_bindings::vpiReturn,                            // return from a sequence
+    #[cfg(feature = "ieee1800-2005")] AnyPattern = sv_bindings::vpiAnyPattern,                    // .* pattern
+    #[cfg(feature = "ieee1800-2005")] TaggedPattern = sv_bindings::vpiTaggedPattern,              // tagged pattern
+    #[cfg(feature = "ieee1800-2005")] StructPattern = sv_bindings::vpiStructPattern,              // struct pattern
+    #[cfg(feature = "ieee1800-2005")] DoWhile = sv_bindings::vpiDoWhile,                          // do-while statement
+    #[cfg(feature = "ieee1800-2005")] OrderedWait = sv_bindings::vpiOrderedWait,                  // wait_order statement
+    #[cfg(feature = "ieee1800-2005")] WaitFork = sv_bindings::vpiWaitFork,                        // wait fork statement
+    #[cfg(feature = "ieee1800-2005")] DisableFork = sv_bindings::vpiDisableFork,                  // disable fork statement
+    #[cfg(feature = "ieee1800-2005")] ExpectStmt = sv_bindings::vpiExpectStmt,                    // expect statement
+    #[cfg(feature = "ieee1800-2005")] ForeachStmt = sv_bindings::vpiForeachStmt,                  // foreach statement
+    #[cfg(feature = "ieee1800-2005")] Final = sv_bindings::vpiFinal,                              // final procedure
+    #[cfg(feature = "ieee1800-2005")] Extends = sv_bindings::vpiExtends,                          // class extends clause
+    #[cfg(feature = "ieee1800-2005")] Distribution = sv_bindings::vpiDistribution,                // dist constraint
+    #[cfg(feature = "ieee1800-2005")] SeqFormalDecl = sv_bindings::vpiSeqFormalDecl,              // sequence formal argument
+    #[cfg(feature = "ieee1800-2005")] EnumNet = sv_bindings::vpiEnumNet,                          // enum net
+    #[cfg(feature = "ieee1800-2005")] IntegerNet = sv_bindings::vpiIntegerNet,                    // integer net
+    #[cfg(feature = "ieee1800-2005")] TimeNet = sv_bindings::vpiTimeNet,                          // time net
+    #[cfg(feature = "ieee1800-2005")] StructNet = sv_bindings::vpiStructNet,                      // struct net
+    #[cfg(feature = "ieee1800-2005")] Break = sv_bindings::vpiBreak,                              // break statement
+    #[cfg(feature = "ieee1800-2005")] Continue = sv_bindings::vpiContinue,                        // continue statement
+    #[cfg(feature = "ieee1800-2005")] Assert = sv_bindings::vpiAssert,                            // concurrent assert
+    #[cfg(feature = "ieee1800-2005")] Assume = sv_bindings::vpiAssume,                            // concurrent assume
+    #[cfg(feature = "ieee1800-2005")] Cover = sv_bindings::vpiCover,                              // concurrent cover
+    #[cfg(feature = "ieee1800-2005")] DisableCondition = sv_bindings::vpiDisableCondition,        // disable iff condition
+    #[cfg(feature = "ieee1800-2005")] ClockingEvent = sv_bindings::vpiClockingEvent,              // clocking event of an assertion
+    #[cfg(feature = "ieee1800-2005")] ReturnStmt = sv_bindings::vpiReturnStmt,                    // return statement
+    #[cfg(feature = "ieee1800-2005")] ConstraintExpr = sv_bindings::vpiConstraintExpr,            // constraint expression
+    #[cfg(feature = "ieee1800-2005")] ElseConst = sv_bindings::vpiElseConst,                      // else branch of a constraint
+    #[cfg(feature = "ieee1800-2005")] Implication = sv_bindings::vpiImplication,                  // constraint implication
+    #[cfg(feature = "ieee1800-2005")] ConstrIf = sv_bindings::vpiConstrIf,                        // constraint if
+    #[cfg(feature = "ieee1800-2005")] ConstrIfElse = sv_bindings::vpiConstrIfElse,                // constraint if-else
+    #[cfg(feature = "ieee1800-2005")] ConstrForEach = sv_bindings::vpiConstrForEach,              // constraint foreach
+    #[cfg(feature = "ieee1800-2005")] Assertion = sv_bindings::vpiAssertion,                      // any concurrent assertion (for iterating)
+
+    // Object types added with 1800-2009
+    #[cfg(feature = "ieee1800-2009")] PackedArrayVar = sv_bindings::vpiPackedArrayVar,            // packed array variable
+    #[cfg(feature = "ieee1800-2009")] VirtualInterfaceVar = sv_bindings::vpiVirtualInterfaceVar,  // virtual interface variable
+    #[cfg(feature = "ieee1800-2009")] PackedArrayTypespec = sv_bindings::vpiPackedArrayTypespec,  // packed array typespec
+    #[cfg(feature = "ieee1800-2009")] SequenceTypespec = sv_bindings::vpiSequenceTypespec,        // sequence typespec
+    #[cfg(feature = "ieee1800-2009")] PropertyTypespec = sv_bindings::vpiPropertyTypespec,        // property typespec
+    #[cfg(feature = "ieee1800-2009")] EventTypespec = sv_bindings::vpiEventTypespec,              // event typespec
+    #[cfg(feature = "ieee1800-2009")] Restrict = sv_bindings::vpiRestrict,                        // concurrent restrict
+    #[cfg(feature = "ieee1800-2009")] ClockedProp = sv_bindings::vpiClockedProp,                  // clocked property
+    #[cfg(feature = "ieee1800-2009")] CasePropertyItem = sv_bindings::vpiCasePropertyItem,        // property case item
+    #[cfg(feature = "ieee1800-2009")] ImmediateAssume = sv_bindings::vpiImmediateAssume,          // immediate assume
+    #[cfg(feature = "ieee1800-2009")] ImmediateCover = sv_bindings::vpiImmediateCover,            // immediate cover
+    #[cfg(feature = "ieee1800-2009")] PropFormalDecl = sv_bindings::vpiPropFormalDecl,            // property formal argument
+    #[cfg(feature = "ieee1800-2009")] PackedArrayNet = sv_bindings::vpiPackedArrayNet,            // packed array net
+    #[cfg(feature = "ieee1800-2009")] LetDecl = sv_bindings::vpiLetDecl,                          // let declaration
+    #[cfg(feature = "ieee1800-2009")] LetExpr = sv_bindings::vpiLetExpr,                          // let expression
+
+    // Object types added with 1800-2012
+    #[cfg(feature = "ieee1800-2012")] SoftDisable = sv_bindings::vpiSoftDisable,                  // disable soft constraint
+    #[cfg(feature = "ieee1800-2012")] InterfaceTypespec = sv_bindings::vpiInterfaceTypespec,      // interface typespec
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(i32)]
+pub enum Property {
+    Type = sv_bindings::vpiType,                            // type of object
+    Name = sv_bindings::vpiName,                            // local name of object
+    FullName = sv_bindings::vpiFullName,                    // full hierarchical name
+    Size = sv_bindings::vpiSize,                            // size of gate, net, port, etc.
+    File = sv_bindings::vpiFile,                            // file name in which the object is used
+    LineNo = sv_bindings::vpiLineNo,                        // line number where the object is used
+    TopModule = sv_bindings::vpiTopModule,                  // top-level module (Boolean)
+    CellInstance = sv_bindings::vpiCellInstance,            // cell (Boolean)
+    DefName = sv_bindings::vpiDefName,                      // module definition name
+    Protected = sv_bindings::vpiProtected,                  // source protected module (Boolean)
+    TimeUnit = sv_bindings::vpiTimeUnit,                    // module time unit
+    TimePrecision = sv_bindings::vpiTimePrecision,          // module time precision
+    DefNetType = sv_bindings::vpiDefNetType,                // default net type
+    UnconnDrive = sv_bindings::vpiUnconnDrive,              // unconnected port drive strength
+    DefFile = sv_bindings::vpiDefFile,                      // file name where the module is defined
+    DefLineNo = sv_bindings::vpiDefLineNo,                  // line number for module definition
+    DefDelayMode = sv_bindings::vpiDefDelayMode,            // default delay mode for a module
+    DefDecayTime = sv_bindings::vpiDefDecayTime,            // default decay time for a module
+    Scalar = sv_bindings::vpiScalar,                        // scalar (Boolean)
+    Vector = sv_bindings::vpiVector,                        // vector (Boolean)
+    ExplicitName = sv_bindings::vpiExplicitName,            // port is explicitly named
+    Direction = sv_bindings::vpiDirection,                  // direction of port
+    ConnByName = sv_bindings::vpiConnByName,                // connected by name (Boolean)
+    NetType = sv_bindings::vpiNetType,                      // net subtype
+    ExplicitScalared = sv_bindings::vpiExplicitScalared,    // explicitly scalared (Boolean)
+    ExplicitVectored = sv_bindings::vpiExplicitVectored,    // explicitly vectored (Boolean)
+    Expanded = sv_bindings::vpiExpanded,                    // expanded vector net (Boolean)
+    ImplicitDecl = sv_bindings::vpiImplicitDecl,            // implicitly declared net (Boolean)
+    ChargeStrength = sv_bindings::vpiChargeStrength,        // charge decay strength of net
+    Array = sv_bindings::vpiArray,                          // variable array (Boolean)
+    PortIndex = sv_bindings::vpiPortIndex,                  // port index
+    TermIndex = sv_bindings::vpiTermIndex,                  // index of a primitive terminal
+    Strength0 = sv_bindings::vpiStrength0,                  // 0-strength of net or gate
+    Strength1 = sv_bindings::vpiStrength1,                  // 1-strength of net or gate
+    PrimType = sv_bindings::vpiPrimType,                    // primitive subtype
+    Polarity = sv_bindings::vpiPolarity,                    // polarity of module path...
+    DataPolarity = sv_bindings::vpiDataPolarity,            // ...or data path
+    Edge = sv_bindings::vpiEdge,                            // edge type of module path
+    PathType = sv_bindings::vpiPathType,                    // path delay connection subtype
+    TchkType = sv_bindings::vpiTchkType,                    // timing check subtype
+    OpType = sv_bindings::vpiOpType,                        // operation subtype
+    ConstType = sv_bindings::vpiConstType,                  // constant subtype
+    Blocking = sv_bindings::vpiBlocking,                    // blocking assignment (Boolean)
+    CaseType = sv_bindings::vpiCaseType,                    // case statement subtype
+    NetDeclAssign = sv_bindings::vpiNetDeclAssign,          // assign part of decl (Boolean)
+    FuncType = sv_bindings::vpiFuncType,                    // function & system function type
+    UserDefn = sv_bindings::vpiUserDefn,                    // user-defined system task/func (Boolean)
+    Scheduled = sv_bindings::vpiScheduled,                  // object still scheduled (Boolean)
+
+    // Properties added with 1364-2001
+    Active = sv_bindings::vpiActive,                        // reentrant task/func frame is active
+    Automatic = sv_bindings::vpiAutomatic,                  // task/func obj is automatic
+    Cell = sv_bindings::vpiCell,                            // configuration cell
+    Config = sv_bindings::vpiConfig,                        // configuration config file
+    ConstantSelect = sv_bindings::vpiConstantSelect,        // bit-select or part-select indices are constant
+    Decompile = sv_bindings::vpiDecompile,                  // decompile the object
+    DefAttribute = sv_bindings::vpiDefAttribute,            // attribute defined for the obj
+    DelayType = sv_bindings::vpiDelayType,                  // delay subtype
+    IteratorType = sv_bindings::vpiIteratorType,            // object type of an iterator
+    Library = sv_bindings::vpiLibrary,                      // configuration library
+    Offset = sv_bindings::vpiOffset,                        // offset from LSB
+    ResolvedNetType = sv_bindings::vpiResolvedNetType,      // net subtype after resolution
+    SaveRestartID = sv_bindings::vpiSaveRestartID,          // unique ID for save/restart data
+    SaveRestartLocation = sv_bindings::vpiSaveRestartLocation, // name of save/restart data file
+    Valid = sv_bindings::vpiValid,                          // frame or automatic variable is valid
+    Signed = sv_bindings::vpiSigned,                        // TRUE for vpiIODecl and any object in the expression class
+
+    // Properties added with 1364-2005
+    LocalParam = sv_bindings::vpiLocalParam,                // TRUE when a param is declared as a localparam
+    ModPathHasIfNone = sv_bindings::vpiModPathHasIfNone,    // mod path has an ifnone statement
+    IndexedPartSelectType = sv_bindings::vpiIndexedPartSelectType, // indexed part-select type
+    IsMemory = sv_bindings::vpiIsMemory,                    // TRUE for a one-dimensional reg array
+    IsProtected = sv_bindings::vpiIsProtected,              // TRUE for protected design information
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(i32)]
+pub enum Relationship {
+    Condition = sv_bindings::vpiCondition,          // condition expression
+    Delay = sv_bindings::vpiDelay,                  // net or gate delay
+    ElseStmt = sv_bindings::vpiElseStmt,            // else statement
+    ForIncStmt = sv_bindings::vpiForIncStmt,        // increment statement in for loop
+    ForInitStmt = sv_bindings::vpiForInitStmt,      // initialization statement in for loop
+    HighConn = sv_bindings::vpiHighConn,            // higher connection to port
+    Lhs = sv_bindings::vpiLhs,                      // left-hand side of assignment
+    Index = sv_bindings::vpiIndex,                  // index of var select, bit-select, etc.
+    LeftRange = sv_bindings::vpiLeftRange,          // left range of vector or part-select
+    LowConn = sv_bindings::vpiLowConn,              // lower connection to port
+    Parent = sv_bindings::vpiParent,                // parent object
+    Rhs = sv_bindings::vpiRhs,                      // right-hand side of assignment
+    RightRange = sv_bindings::vpiRightRange,        // right range of vector or part-select
+    Scope = sv_bindings::vpiScope,                  // containing scope object
+    SysTfCall = sv_bindings::vpiSysTfCall,          // task function call
+    TchkDataTerm = sv_bindings::vpiTchkDataTerm,    // timing check data term
+    TchkNotifier = sv_bindings::vpiTchkNotifier,    // timing check notifier
+    TchkRefTerm = sv_bindings::vpiTchkRefTerm,      // timing check reference term
+    Expr = sv_bindings::vpiExpr,                    // connected expression
+    Primitive = sv_bindings::vpiPrimitive,          // primitive (gate, switch, UDP)
+    Stmt = sv_bindings::vpiStmt,                    // statement in process or task
+    Module = sv_bindings::vpiModule,                // module containing the object
+    Task = sv_bindings::vpiTask,                    // task containing the object or called by a task call
+    Function = sv_bindings::vpiFunction,            // function containing the object or called by a function call
+    UdpDefn = sv_bindings::vpiUdpDefn,              // definition of a UDP instance
+    UserSystf = sv_bindings::vpiUserSystf,          // user-defined system task/function of a call
+
+    // Methods added with 1364-2001
+    ActiveTimeFormat = sv_bindings::vpiActiveTimeFormat,    // active $timeformat() system task
+    InstanceArray = sv_bindings::vpiInstanceArray,          // instance array containing the object
+    SimNet = sv_bindings::vpiSimNet,                        // simulated net after collapsing
+
+    // Methods added with 1364-2005
+    BaseExpr = sv_bindings::vpiBaseExpr,                    // indexed part-select's base expression
+    WidthExpr = sv_bindings::vpiWidthExpr,                  // indexed part-select's width expression
+
+    // Methods added with 1800-2005
+    #[cfg(feature = "ieee1800-2005")] Typespec = sv_bindings::vpiTypespec,            // typespec of the object
+    #[cfg(feature = "ieee1800-2005")] Actual = sv_bindings::vpiActual,                // object actually referenced
+    #[cfg(feature = "ieee1800-2005")] BaseTypespec = sv_bindings::vpiBaseTypespec,    // typespec a typespec is derived from
+    #[cfg(feature = "ieee1800-2005")] ElemTypespec = sv_bindings::vpiElemTypespec,    // typespec of the elements of an array
+    #[cfg(feature = "ieee1800-2005")] Instance = sv_bindings::vpiInstance,            // instance containing the object
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(i32)]
+pub enum ManyRelationship {
+    Argument = sv_bindings::vpiArgument,            // argument to (system) task/function
+    Bit = sv_bindings::vpiBit,                      // bit of vector net or port
+    Driver = sv_bindings::vpiDriver,                // driver for a net
+    InternalScope = sv_bindings::vpiInternalScope,  // internal scope in module
+    Load = sv_bindings::vpiLoad,                    // load on net or reg
+    ModDataPathIn = sv_bindings::vpiModDataPathIn,  // data terminal of a module path
+    ModPathIn = sv_bindings::vpiModPathIn,          // input terminal of a module path
+    ModPathOut = sv_bindings::vpiModPathOut,        // output terminal of a module path
+    Operand = sv_bindings::vpiOperand,              // operand of expression
+    PortInst = sv_bindings::vpiPortInst,            // connected port instance
+    Process = sv_bindings::vpiProcess,              // process in module, program or interface
+    Variables = sv_bindings::vpiVariables,          // variables in module
+    Use = sv_bindings::vpiUse,                      // usage
+    Expr = sv_bindings::vpiExpr,                    // connected expression
+    Primitive = sv_bindings::vpiPrimitive,          // primitive (gate, switch, UDP)
+    Stmt = sv_bindings::vpiStmt,                    // statement in process or task
+
+    // Methods added with 1364-2001
+    InTerm = sv_bindings::vpiInTerm,                // to get to a delay device's drivers
+    LocalDriver = sv_bindings::vpiLocalDriver,      // local drivers (within a module)
+    LocalLoad = sv_bindings::vpiLocalLoad,          // local loads (within a module)
+    OutTerm = sv_bindings::vpiOutTerm,              // to get to a delay device's loads
+    Ports = sv_bindings::vpiPorts,                  // module port
+    TaskFunc = sv_bindings::vpiTaskFunc,            // task/function
+
+    // Methods added with 1800-2005
+    #[cfg(feature = "ieee1800-2005")] Typedef = sv_bindings::vpiTypedef,                          // typedefs in a scope
+    #[cfg(feature = "ieee1800-2005")] Import = sv_bindings::vpiImport,                            // package imports
+    #[cfg(feature = "ieee1800-2005")] DerivedClasses = sv_bindings::vpiDerivedClasses,            // classes extending a class
+    #[cfg(feature = "ieee1800-2005")] Methods = sv_bindings::vpiMethods,                          // methods of a class
+    #[cfg(feature = "ieee1800-2005")] ConcurrentAssertions = sv_bindings::vpiConcurrentAssertions, // concurrent assertions in a scope
+    #[cfg(feature = "ieee1800-2005")] Member = sv_bindings::vpiMember,                            // members of a struct/union
+    #[cfg(feature = "ieee1800-2005")] Element = sv_bindings::vpiElement,                          // elements of an array
+    #[cfg(feature = "ieee1800-2005")] Instance = sv_bindings::vpiInstance,                        // instances in a scope
 }
 
 /* ------------------------------------------------------------------------------------------------
  * Associated Functions and Methods
  * --------------------------------------------------------------------------------------------- */
 
+///SystemVerilog names for object types that share their value with a Verilog one
+#[cfg(feature = "ieee1800-2005")]
+#[allow(non_upper_case_globals)]
+impl ObjectType {
+    pub const VarBit: ObjectType = ObjectType::RegBit;
+    pub const LogicVar: ObjectType = ObjectType::Reg;
+    pub const ArrayVar: ObjectType = ObjectType::RegArray;
+    pub const LogicNet: ObjectType = ObjectType::Net;
+    pub const ArrayNet: ObjectType = ObjectType::NetArray;
+}
+
+impl ObjectType {
+    ///Looks up one of the SystemVerilog alias names (without the "vpi" prefix)
+    fn from_alias(name: &str) -> Option<ObjectType> {
+        match name {
+            #[cfg(feature = "ieee1800-2005")] "VarBit"      => Some(ObjectType::VarBit),
+            #[cfg(feature = "ieee1800-2005")] "LogicVar"    => Some(ObjectType::LogicVar),
+            #[cfg(feature = "ieee1800-2005")] "ArrayVar"    => Some(ObjectType::ArrayVar),
+            #[cfg(feature = "ieee1800-2005")] "LogicNet"    => Some(ObjectType::LogicNet),
+            #[cfg(feature = "ieee1800-2005")] "ArrayNet"    => Some(ObjectType::ArrayNet),
+            _                                               => None
+        }
+    }
+}
+
+impl Property {
+    ///Decides what vpi_get() returning vpiUndefined means for this property
+    ///
+    ///-1 is also a valid value for the time properties (the exponent of 100ms), so for those only
+    ///vpi_chk_error() can tell whether something actually went wrong.
+    fn check_undefined<T>(self, raw_value: T) -> Result<T> {
+        let minus_one_is_valid = matches!(self, Property::TimeUnit | Property::TimePrecision);
+        match result::last_vpi_error() {
+            Some(error)                 => Err(error),
+            None if minus_one_is_valid  => Ok(raw_value),
+            None                        => Err(Box::new(Error::UndefinedProperty(self)))
+        }
+    }
+}
+
+impl ObjectHandle {
+    ///Wraps a raw handle from the simulator, returning None if it is null
+    fn from_raw(raw_handle: sv_bindings::vpiHandle) -> Option<ObjectHandle> {
+        std::ptr::NonNull::new(raw_handle).map(|handle| ObjectHandle { handle })
+    }
+
+    ///Looks up an object by its full hierarchical name (ex. "top.h.b")
+    pub fn by_name(name: &str) -> Result<ObjectHandle> {
+        ObjectHandle::handle_by_name(name, std::ptr::null_mut())
+    }
+
+    ///Looks up an object by its name relative to this scope (ex. "b" within "top.h")
+    pub fn child_by_name(&self, name: &str) -> Result<ObjectHandle> {
+        ObjectHandle::handle_by_name(name, self.handle.as_ptr())
+    }
+
+    fn handle_by_name(name: &str, scope: sv_bindings::vpiHandle) -> Result<ObjectHandle> {
+        panic_if_in_startup_routine!();
+        panic_if_not_main_thread!();
+
+        let cstring = std::ffi::CString::new(name).map_err(|e| Box::new(Error::Other(Box::new(e))))?;
+
+        //SAFETY: It is safe to cast to *mut PLI_BYTE8 because vpi_handle_by_name() does not modify
+        //the string. We're calling it from the main thread after startup routines have finished,
+        //and the scope is either null (search from the top) or a valid handle.
+        let raw_handle = unsafe {
+            sv_bindings::vpi_handle_by_name(cstring.as_ptr() as *mut sv_bindings::PLI_BYTE8, scope)
+        };
+
+        ObjectHandle::from_raw(raw_handle).ok_or_else(|| {
+            result::last_vpi_error().unwrap_or(Box::new(Error::NameNotFound(name.to_string())))
+        })
+    }
+
+    ///Gets an integer property of the object (using `vpi_get()`)
+    pub fn get(&self, property: Property) -> Result<i32> {
+        panic_if_in_startup_routine!();
+        panic_if_not_main_thread!();
+
+        //SAFETY: We're calling vpi_get() from the main thread after startup routines have finished,
+        //and the handle is guaranteed to be valid and non-null
+        let raw_value = unsafe { sv_bindings::vpi_get(property as i32, self.handle.as_ptr()) };
+
+        if raw_value == sv_bindings::vpiUndefined {
+            property.check_undefined(raw_value)
+        } else {
+            Ok(raw_value)
+        }
+    }
+
+    ///Gets a 64-bit integer property of the object (using `vpi_get64()`)
+    pub fn get64(&self, property: Property) -> Result<i64> {
+        panic_if_in_startup_routine!();
+        panic_if_not_main_thread!();
+
+        //SAFETY: We're calling vpi_get64() from the main thread after startup routines have
+        //finished, and the handle is guaranteed to be valid and non-null
+        let raw_value = unsafe { sv_bindings::vpi_get64(property as i32, self.handle.as_ptr()) };
+
+        if raw_value == sv_bindings::vpiUndefined as i64 {
+            property.check_undefined(raw_value)
+        } else {
+            Ok(raw_value)
+        }
+    }
+
+    ///Gets a boolean property of the object (using `vpi_get()`)
+    pub fn get_bool(&self, property: Property) -> Result<bool> {
+        Ok(self.get(property)? != 0)
+    }
+
+    ///Gets a string property of the object (using `vpi_get_str()`)
+    pub fn get_str(&self, property: Property) -> Result<String> {
+        panic_if_in_startup_routine!();
+        panic_if_not_main_thread!();
+
+        //SAFETY: We're calling vpi_get_str() from the main thread after startup routines have
+        //finished, and the handle is guaranteed to be valid and non-null
+        let raw_str = unsafe { sv_bindings::vpi_get_str(property as i32, self.handle.as_ptr()) };
+
+        if raw_str.is_null() {
+            return Err(result::last_vpi_error().unwrap_or(Box::new(Error::UndefinedProperty(property))));
+        }
+
+        //SAFETY: We should have been given a valid null-terminated string. It lives in a buffer
+        //owned by the simulator that the next call may overwrite, so we copy it out right away.
+        Ok(unsafe { std::ffi::CStr::from_ptr(raw_str) }.to_string_lossy().into_owned())
+    }
+
+    pub fn name(&self) -> Result<String> {
+        self.get_str(Property::Name)
+    }
+
+    pub fn full_name(&self) -> Result<String> {
+        self.get_str(Property::FullName)
+    }
+
+    pub fn def_name(&self) -> Result<String> {
+        self.get_str(Property::DefName)
+    }
+
+    ///The size of the object in bits (or the number of elements/ports/etc. depending on the type)
+    pub fn size(&self) -> Result<usize> {
+        let raw_size = self.get(Property::Size)?;
+        raw_size.try_into().map_err(|_| Box::new(Error::UndefinedProperty(Property::Size)))
+    }
+
+    pub fn is_signed(&self) -> Result<bool> {
+        self.get_bool(Property::Signed)
+    }
+
+    pub fn is_vector(&self) -> Result<bool> {
+        self.get_bool(Property::Vector)
+    }
+
+    pub fn object_type(&self) -> Result<ObjectType> {
+        self.get(Property::Type)?.try_into()
+    }
+
+    ///Follows a one-to-one relationship to another object (using `vpi_handle()`)
+    pub fn related(&self, relationship: Relationship) -> Result<ObjectHandle> {
+        panic_if_in_startup_routine!();
+        panic_if_not_main_thread!();
+
+        //SAFETY: We're calling vpi_handle() from the main thread after startup routines have
+        //finished, and the handle is guaranteed to be valid and non-null
+        let raw_handle = unsafe { sv_bindings::vpi_handle(relationship as i32, self.handle.as_ptr()) };
+
+        ObjectHandle::from_raw(raw_handle).ok_or_else(|| {
+            result::last_vpi_error().unwrap_or(Box::new(Error::NoRelatedObject(relationship)))
+        })
+    }
+
+    ///Iterates over the objects on the other end of a one-to-many relationship
+    pub fn related_iter(&self, relationship: ManyRelationship) -> ObjectIterator {
+        ObjectIterator::new_with_relationship(relationship, self)
+    }
+}
+
 impl ObjectIterator {
-    fn new(object_type: ObjectType) -> ObjectIterator {
+    ///Iterates over all objects of the given type at the top of the design hierarchy
+    pub fn new(object_type: ObjectType) -> ObjectIterator {
         startup::panic_if_in_startup_routine!();
         //FIXME justify safety
-        let raw_handle = unsafe { sv_bindings::vpi_iterate(object_type as i32, std::ptr::null_mut()) };
-
-        let iterator_handle = if raw_handle.is_null() {
-            None
-        } else {
-            Some(ObjectHandle { handle: std::ptr::NonNull::new(raw_handle).unwrap() })
-        };
+        let raw_handle = unsafe { sv_bindings::vpi_iterate(object_type.into(), std::ptr::null_mut()) };
 
+        //A null iterator handle just means there is nothing to iterate over
         ObjectIterator {
-            iterator_handle: iterator_handle
+            iterator_handle: ObjectHandle::from_raw(raw_handle)
         }
     }
 
-    fn new_with_reference(object_type: ObjectType, reference: &mut ObjectHandle) -> ObjectIterator {
+    ///Iterates over all objects of the given type related to (usually contained in) the reference
+    pub fn new_with_reference(object_type: ObjectType, reference: &ObjectHandle) -> ObjectIterator {
         startup::panic_if_in_startup_routine!();
         //FIXME justify safety
-        let raw_handle = unsafe { sv_bindings::vpi_iterate(object_type as i32, reference.handle.as_ptr()) };
+        let raw_handle = unsafe { sv_bindings::vpi_iterate(object_type.into(), reference.handle.as_ptr()) };
 
-        let iterator_handle = if raw_handle.is_null() {
-            None
-        } else {
-            Some(ObjectHandle { handle: std::ptr::NonNull::new(raw_handle).unwrap() })
-        };
+        //A null iterator handle just means there is nothing to iterate over
+        ObjectIterator {
+            iterator_handle: ObjectHandle::from_raw(raw_handle)
+        }
+    }
 
+    ///Iterates over all objects on the other end of a one-to-many relationship with the reference
+    pub fn new_with_relationship(relationship: ManyRelationship, reference: &ObjectHandle) -> ObjectIterator {
+        panic_if_in_startup_routine!();
+        panic_if_not_main_thread!();
+
+        //SAFETY: We're calling vpi_iterate() from the main thread after startup routines have
+        //finished, and the reference handle is guaranteed to be valid and non-null
+        let raw_handle = unsafe { sv_bindings::vpi_iterate(relationship as i32, reference.handle.as_ptr()) };
+
+        //A null iterator handle just means there is nothing to iterate over
         ObjectIterator {
-            iterator_handle: iterator_handle
+            iterator_handle: ObjectHandle::from_raw(raw_handle)
         }
     }
 }
@@ -209,16 +754,49 @@ impl ObjectIterator {
  * Trait Implementations
  * --------------------------------------------------------------------------------------------- */
 
+impl PartialEq for ObjectHandle {
+    fn eq(&self, other: &ObjectHandle) -> bool {
+        if self.handle == other.handle {
+            return true;//Same pointer, so definitely the same object
+        }
+
+        panic_if_in_startup_routine!();
+        panic_if_not_main_thread!();
+
+        //Different handles may still refer to the same object, so we need to ask the simulator
+        //SAFETY: We're calling vpi_compare_objects() from the main thread after startup routines
+        //have finished, and both handles are guaranteed to be valid and non-null
+        unsafe { sv_bindings::vpi_compare_objects(self.handle.as_ptr(), other.handle.as_ptr()) == 1 }
+    }
+}
+
+impl Eq for ObjectHandle {}
+
+impl std::hash::Hash for ObjectHandle {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        //Handles to the same object may have different pointers, so we can't hash those.
+        //Instead we use properties that are the same for equal objects: the full name and type.
+        //Not every object has these, but that only leads to more collisions, not incorrectness.
+        self.full_name().ok().hash(state);
+        self.get(Property::Type).ok().hash(state);
+    }
+}
+
 impl Drop for ObjectHandle {
     fn drop(&mut self) {
-        //Guaranteed the handle is not null (it is a NonNull)
-        //FIXME test this on a simulator that supports it
-        /*
-        unsafe {
-            //FIXME justify safety
-            sv_bindings::vpi_release_handle(handle.as_ptr());
+        //Guaranteed the handle is not null (it is a NonNull). ObjectHandle isn't Send, so we're
+        //still on the main thread, and handles only exist once startup routines have finished.
+        let raw_handle = self.handle.as_ptr();
+
+        //vpi_release_handle() is from 1800, while older simulators only have vpi_free_object() from
+        //1364. We try both every time rather than remembering which one worked, since a failure
+        //could just as well be about this particular handle. If neither works, we leave it to the
+        //simulator to clean up.
+        //SAFETY: We own the handle and it is never used again after this, so releasing it is fine.
+        //vpi_free_object() is only tried if vpi_release_handle() didn't release it.
+        if unsafe { sv_bindings::vpi_release_handle(raw_handle) } != 1 {
+            unsafe { sv_bindings::vpi_free_object(raw_handle) };
         }
-        */
     }
 }
 
@@ -234,16 +812,21 @@ impl Iterator for ObjectIterator {
         };
 
         if raw_handle_from_scan.is_null() {
-            //FIXME I actually don't think we should release the iterator handle in this case (scan
-            //being null already takes care of that)
-            self.iterator_handle = None;//Iterator handle is now invalid (this drops it)
+            //vpi_scan() already freed the iterator when it returned null, so we must forget the
+            //handle rather than drop it (which would release it a second time). If the iterator is
+            //instead abandoned early, dropping the ObjectIterator releases it normally.
+            if let Some(exhausted_iterator_handle) = self.iterator_handle.take() {
+                std::mem::forget(exhausted_iterator_handle);
+            }
             None
         } else {
-            Some(ObjectHandle { handle: std::ptr::NonNull::new(raw_handle_from_scan).unwrap() })
+            ObjectHandle::from_raw(raw_handle_from_scan)
         }
     }
 }
 
+impl std::iter::FusedIterator for ObjectIterator {}
+
 /* ------------------------------------------------------------------------------------------------
  * Functions
  * --------------------------------------------------------------------------------------------- */
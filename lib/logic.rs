@@ -0,0 +1,702 @@
+/*
+ * File:    logic.rs
+ * Brief:   Four-state logic values (0, 1, X and Z), both single bits and arbitrary-width vectors.
+ *
+ * Copyright (C) 2023 John Jekel
+ * See the LICENSE file at the root of the project for licensing info.
+ *
+ * LogicVec is stored the same way as an array of s_vpi_vecval: 32 bits per word, each word split
+ * into an aval and a bval, so converting to and from what the simulator uses is cheap.
+ *
+*/
+
+/*!
+ * Four-state logic values (0, 1, X and Z).
+ *
+ * [`Logic`] is a single bit and [`LogicVec`] is an arbitrary-width vector of them. Bit 0 of a
+ * [`LogicVec`] is the least significant bit, just like `[N-1:0]` in SystemVerilog.
+ *
+ * Converting from plain integers always succeeds, while converting back ([`TryFrom`]) fails if any bit
+ * is X or Z, or if the value doesn't fit.
+ *
+ * The bitwise operators follow SystemVerilog semantics: `0 & X == 0`, `1 | X == 1`, Z is treated
+ * like X, and the narrower operand is zero-extended to the width of the wider one.
+ *
+*/
+
+/* ------------------------------------------------------------------------------------------------
+ * Uses
+ * --------------------------------------------------------------------------------------------- */
+
+use std::fmt;
+use std::fmt::Display;
+use std::ops::{BitAnd, BitOr, BitXor, Bound, Not, RangeBounds};
+use std::str::FromStr;
+
+use crate::result::{Error, Result};
+
+/* ------------------------------------------------------------------------------------------------
+ * Constants
+ * --------------------------------------------------------------------------------------------- */
+
+const BITS_PER_WORD: usize = 32;
+
+/* ------------------------------------------------------------------------------------------------
+ * Types
+ * --------------------------------------------------------------------------------------------- */
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(i32)]
+pub enum Logic {
+    Zero = sv_bindings::vpi0,
+    One = sv_bindings::vpi1,
+    Z = sv_bindings::vpiZ,
+    X = sv_bindings::vpiX
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct LogicVec {
+    width: usize,
+    //Encoding for each bit, ab: 00 = 0, 10 = 1, 11 = X, 01 = Z (same as s_vpi_vecval)
+    //Bits past the width in the last word are always 00 so that derived comparisons work
+    words: Vec<Word>
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+struct Word {
+    aval: u32,
+    bval: u32
+}
+
+/* ------------------------------------------------------------------------------------------------
+ * Associated Functions and Methods
+ * --------------------------------------------------------------------------------------------- */
+
+impl Logic {
+    ///True if the bit is X or Z
+    pub fn is_unknown(self) -> bool {
+        matches!(self, Logic::X | Logic::Z)
+    }
+
+    fn from_ab(aval: bool, bval: bool) -> Logic {
+        match (aval, bval) {
+            (false, false)  => Logic::Zero,
+            (true, false)   => Logic::One,
+            (true, true)    => Logic::X,
+            (false, true)   => Logic::Z
+        }
+    }
+
+    fn to_ab(self) -> (bool, bool) {
+        match self {
+            Logic::Zero => (false, false),
+            Logic::One  => (true, false),
+            Logic::X    => (true, true),
+            Logic::Z    => (false, true)
+        }
+    }
+}
+
+impl LogicVec {
+    ///A vector of the given width with every bit set to `bit`
+    pub fn filled(width: usize, bit: Logic) -> LogicVec {
+        let (aval, bval) = bit.to_ab();
+        let word = Word {
+            aval: if aval { u32::MAX } else { 0 },
+            bval: if bval { u32::MAX } else { 0 }
+        };
+        let mut vector = LogicVec {
+            width,
+            words: vec![word; width.div_ceil(BITS_PER_WORD)]
+        };
+        vector.clear_unused_bits();
+        vector
+    }
+
+    pub fn zeros(width: usize) -> LogicVec {
+        LogicVec::filled(width, Logic::Zero)
+    }
+
+    pub fn from_u64(value: u64, width: usize) -> LogicVec {
+        LogicVec::from_u128(value as u128, width)
+    }
+
+    ///Values wider than the width are truncated, and narrower ones are zero-extended
+    pub fn from_u128(value: u128, width: usize) -> LogicVec {
+        let mut vector = LogicVec::zeros(width);
+        for (i, word) in vector.words.iter_mut().enumerate().take(128 / BITS_PER_WORD) {
+            word.aval = (value >> (i * BITS_PER_WORD)) as u32;
+        }
+        vector.clear_unused_bits();
+        vector
+    }
+
+    ///Little-endian: the first byte holds bits 0 to 7. The width is 8 bits per byte.
+    pub fn from_bytes(bytes: &[u8]) -> LogicVec {
+        let mut vector = LogicVec::zeros(bytes.len() * 8);
+        for (i, byte) in bytes.iter().enumerate() {
+            vector.words[i / 4].aval |= (*byte as u32) << ((i % 4) * 8);
+        }
+        vector
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    ///Panics if the index is out of bounds
+    pub fn get(&self, index: usize) -> Logic {
+        assert!(index < self.width, "Bit index {} out of bounds for a LogicVec of width {}", index, self.width);
+        let word = self.words[index / BITS_PER_WORD];
+        let bit = index % BITS_PER_WORD;
+        Logic::from_ab((word.aval >> bit) & 1 == 1, (word.bval >> bit) & 1 == 1)
+    }
+
+    ///Panics if the index is out of bounds
+    pub fn set(&mut self, index: usize, value: Logic) {
+        assert!(index < self.width, "Bit index {} out of bounds for a LogicVec of width {}", index, self.width);
+        let (aval, bval) = value.to_ab();
+        let word = &mut self.words[index / BITS_PER_WORD];
+        let mask = 1u32 << (index % BITS_PER_WORD);
+        word.aval = if aval { word.aval | mask } else { word.aval & !mask };
+        word.bval = if bval { word.bval | mask } else { word.bval & !mask };
+    }
+
+    ///Iterates over the bits from least to most significant
+    pub fn iter(&self) -> impl Iterator<Item = Logic> + '_ {
+        (0..self.width).map(|index| self.get(index))
+    }
+
+    ///True if any bit is X or Z
+    pub fn has_unknown(&self) -> bool {
+        self.words.iter().any(|word| word.bval != 0)
+    }
+
+    ///Little-endian: the first byte holds bits 0 to 7. Fails if any bit is X or Z.
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        if self.has_unknown() {
+            return Err(Box::new(Error::UnknownLogicValue));
+        }
+
+        Ok((0..self.width.div_ceil(8)).map(|i| (self.words[i / 4].aval >> ((i % 4) * 8)) as u8).collect())
+    }
+
+    ///Extracts the bits in the range (ex. `vector.slice(4..8)` is like `vector[7:4]`)
+    ///
+    ///Panics if the range is out of bounds
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> LogicVec {
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start + 1,
+            Bound::Unbounded        => 0
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end)   => end + 1,
+            Bound::Excluded(&end)   => end,
+            Bound::Unbounded        => self.width
+        };
+        assert!(start <= end && end <= self.width, "Range {}..{} out of bounds for a LogicVec of width {}", start, end, self.width);
+
+        let mut sliced = LogicVec::zeros(end - start);
+        for index in start..end {
+            sliced.set(index - start, self.get(index));
+        }
+        sliced
+    }
+
+    ///Like `{self, lower}` in SystemVerilog: `self` ends up in the most significant bits
+    pub fn concat(&self, lower: &LogicVec) -> LogicVec {
+        let mut concatenated = LogicVec::zeros(self.width + lower.width);
+        for (index, bit) in lower.iter().chain(self.iter()).enumerate() {
+            concatenated.set(index, bit);
+        }
+        concatenated
+    }
+
+    ///Zero-extends or truncates to the given width
+    pub fn resized(&self, width: usize) -> LogicVec {
+        let mut resized = LogicVec {
+            width,
+            words: self.words.clone()
+        };
+        resized.words.resize(width.div_ceil(BITS_PER_WORD), Word::default());
+        resized.clear_unused_bits();
+        resized
+    }
+
+    ///Converts from the representation used by s_vpi_vecval
+    pub(crate) fn from_vecval(vecval: &[sv_bindings::t_vpi_vecval], width: usize) -> LogicVec {
+        let mut vector = LogicVec {
+            width,
+            words: vecval.iter().map(|word| Word { aval: word.aval, bval: word.bval }).collect()
+        };
+        vector.words.resize(width.div_ceil(BITS_PER_WORD), Word::default());
+        vector.clear_unused_bits();
+        vector
+    }
+
+    ///Converts to the representation used by s_vpi_vecval
+    pub(crate) fn to_vecval(&self) -> Vec<sv_bindings::t_vpi_vecval> {
+        self.words.iter().map(|word| sv_bindings::t_vpi_vecval { aval: word.aval, bval: word.bval }).collect()
+    }
+
+    fn clear_unused_bits(&mut self) {
+        let used_bits = self.width % BITS_PER_WORD;
+        if used_bits != 0 {
+            let mask = (1u32 << used_bits) - 1;
+            let last_word = self.words.last_mut().expect("A non-multiple of 32 width means there is a word");
+            last_word.aval &= mask;
+            last_word.bval &= mask;
+        }
+    }
+
+    ///Applies a word-wise operation after zero-extending both operands to the wider width
+    fn zip_words(&self, other: &LogicVec, op: impl Fn(Word, Word) -> Word) -> LogicVec {
+        let width = self.width.max(other.width);
+        let (lhs, rhs) = (self.resized(width), other.resized(width));
+        let mut result = LogicVec {
+            width,
+            words: lhs.words.iter().zip(rhs.words.iter()).map(|(&l, &r)| op(l, r)).collect()
+        };
+        result.clear_unused_bits();
+        result
+    }
+}
+
+impl Word {
+    ///Bits that are known to be 0
+    fn zeros(self) -> u32 {
+        !self.aval & !self.bval
+    }
+
+    ///Bits that are known to be 1
+    fn ones(self) -> u32 {
+        self.aval & !self.bval
+    }
+
+    ///Builds a word from which bits are 0 and which are 1; everything else becomes X
+    fn from_known(zeros: u32, ones: u32) -> Word {
+        let unknown = !(zeros | ones);
+        Word {
+            aval: ones | unknown,
+            bval: unknown
+        }
+    }
+}
+
+/* ------------------------------------------------------------------------------------------------
+ * Trait Implementations
+ * --------------------------------------------------------------------------------------------- */
+
+impl From<bool> for Logic {
+    fn from(value: bool) -> Logic {
+        if value { Logic::One } else { Logic::Zero }
+    }
+}
+
+impl TryFrom<Logic> for bool {
+    type Error = Box<Error>;
+
+    fn try_from(logic: Logic) -> Result<bool> {
+        match logic {
+            Logic::Zero => Ok(false),
+            Logic::One  => Ok(true),
+            _           => Err(Box::new(Error::UnknownLogicValue))
+        }
+    }
+}
+
+impl Display for Logic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let c = match self {
+            Logic::Zero => '0',
+            Logic::One  => '1',
+            Logic::X    => 'x',
+            Logic::Z    => 'z'
+        };
+        write!(f, "{}", c)
+    }
+}
+
+impl TryFrom<char> for Logic {
+    type Error = Box<Error>;
+
+    fn try_from(c: char) -> Result<Logic> {
+        match c {
+            '0'         => Ok(Logic::Zero),
+            '1'         => Ok(Logic::One),
+            'x' | 'X'   => Ok(Logic::X),
+            'z' | 'Z'   => Ok(Logic::Z),
+            _           => Err(Box::new(Error::InvalidLogicString(c.to_string())))
+        }
+    }
+}
+
+impl Not for Logic {
+    type Output = Logic;
+
+    fn not(self) -> Logic {
+        match self {
+            Logic::Zero => Logic::One,
+            Logic::One  => Logic::Zero,
+            _           => Logic::X
+        }
+    }
+}
+
+impl BitAnd for Logic {
+    type Output = Logic;
+
+    fn bitand(self, rhs: Logic) -> Logic {
+        (LogicVec::from(self) & LogicVec::from(rhs)).get(0)
+    }
+}
+
+impl BitOr for Logic {
+    type Output = Logic;
+
+    fn bitor(self, rhs: Logic) -> Logic {
+        (LogicVec::from(self) | LogicVec::from(rhs)).get(0)
+    }
+}
+
+impl BitXor for Logic {
+    type Output = Logic;
+
+    fn bitxor(self, rhs: Logic) -> Logic {
+        (LogicVec::from(self) ^ LogicVec::from(rhs)).get(0)
+    }
+}
+
+impl From<Logic> for LogicVec {
+    fn from(bit: Logic) -> LogicVec {
+        LogicVec::filled(1, bit)
+    }
+}
+
+impl From<bool> for LogicVec {
+    fn from(value: bool) -> LogicVec {
+        LogicVec::from(Logic::from(value))
+    }
+}
+
+impl From<u64> for LogicVec {
+    fn from(value: u64) -> LogicVec {
+        LogicVec::from_u64(value, 64)
+    }
+}
+
+impl From<u128> for LogicVec {
+    fn from(value: u128) -> LogicVec {
+        LogicVec::from_u128(value, 128)
+    }
+}
+
+impl TryFrom<&LogicVec> for bool {
+    type Error = Box<Error>;
+
+    ///Only succeeds for vectors of width 1
+    fn try_from(vector: &LogicVec) -> Result<bool> {
+        if vector.width != 1 {
+            return Err(Box::new(Error::ValueOutOfRange));
+        }
+        vector.get(0).try_into()
+    }
+}
+
+impl TryFrom<&LogicVec> for u128 {
+    type Error = Box<Error>;
+
+    ///Fails if any bit is X or Z, or if any bit past the 128th is 1
+    fn try_from(vector: &LogicVec) -> Result<u128> {
+        if vector.has_unknown() {
+            return Err(Box::new(Error::UnknownLogicValue));
+        }
+        if vector.words.iter().skip(128 / BITS_PER_WORD).any(|word| word.aval != 0) {
+            return Err(Box::new(Error::ValueOutOfRange));
+        }
+
+        Ok(vector.words.iter().take(128 / BITS_PER_WORD).enumerate().fold(0, |value, (i, word)| {
+            value | ((word.aval as u128) << (i * BITS_PER_WORD))
+        }))
+    }
+}
+
+impl TryFrom<&LogicVec> for u64 {
+    type Error = Box<Error>;
+
+    ///Fails if any bit is X or Z, or if any bit past the 64th is 1
+    fn try_from(vector: &LogicVec) -> Result<u64> {
+        u128::try_from(vector)?.try_into().map_err(|_| Box::new(Error::ValueOutOfRange))
+    }
+}
+
+impl BitAnd for &LogicVec {
+    type Output = LogicVec;
+
+    fn bitand(self, rhs: &LogicVec) -> LogicVec {
+        self.zip_words(rhs, |l, r| Word::from_known(l.zeros() | r.zeros(), l.ones() & r.ones()))
+    }
+}
+
+impl BitOr for &LogicVec {
+    type Output = LogicVec;
+
+    fn bitor(self, rhs: &LogicVec) -> LogicVec {
+        self.zip_words(rhs, |l, r| Word::from_known(l.zeros() & r.zeros(), l.ones() | r.ones()))
+    }
+}
+
+impl BitXor for &LogicVec {
+    type Output = LogicVec;
+
+    fn bitxor(self, rhs: &LogicVec) -> LogicVec {
+        self.zip_words(rhs, |l, r| {
+            let known = !(l.bval | r.bval);
+            let result = l.aval ^ r.aval;
+            Word::from_known(known & !result, known & result)
+        })
+    }
+}
+
+impl Not for &LogicVec {
+    type Output = LogicVec;
+
+    fn not(self) -> LogicVec {
+        self.zip_words(&LogicVec::zeros(0), |word, _| Word::from_known(word.ones(), word.zeros()))
+    }
+}
+
+impl BitAnd for LogicVec {
+    type Output = LogicVec;
+
+    fn bitand(self, rhs: LogicVec) -> LogicVec {
+        &self & &rhs
+    }
+}
+
+impl BitOr for LogicVec {
+    type Output = LogicVec;
+
+    fn bitor(self, rhs: LogicVec) -> LogicVec {
+        &self | &rhs
+    }
+}
+
+impl BitXor for LogicVec {
+    type Output = LogicVec;
+
+    fn bitxor(self, rhs: LogicVec) -> LogicVec {
+        &self ^ &rhs
+    }
+}
+
+impl Not for LogicVec {
+    type Output = LogicVec;
+
+    fn not(self) -> LogicVec {
+        !&self
+    }
+}
+
+///Most significant bit first, like a SystemVerilog binary literal (ex. "10xz")
+impl Display for LogicVec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for index in (0..self.width).rev() {
+            write!(f, "{}", self.get(index))?;
+        }
+        Ok(())
+    }
+}
+
+///Parses a string of 0, 1, x and z, most significant bit first. Underscores are ignored.
+impl FromStr for LogicVec {
+    type Err = Box<Error>;
+
+    fn from_str(string: &str) -> Result<LogicVec> {
+        let bits = string.chars()
+            .filter(|&c| c != '_')
+            .map(|c| Logic::try_from(c).map_err(|_| Box::new(Error::InvalidLogicString(string.to_string()))))
+            .collect::<Result<Vec<Logic>>>()?;
+
+        let mut vector = LogicVec::zeros(bits.len());
+        for (index, bit) in bits.into_iter().rev().enumerate() {
+            vector.set(index, bit);
+        }
+        Ok(vector)
+    }
+}
+
+/* ------------------------------------------------------------------------------------------------
+ * Tests
+ * --------------------------------------------------------------------------------------------- */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [Logic; 4] = [Logic::Zero, Logic::One, Logic::X, Logic::Z];
+
+    ///Checks an operator on bits and the same operator on vectors against a truth table whose rows
+    ///and columns are in the order of ALL
+    fn check_truth_table(
+        bit_op: impl Fn(Logic, Logic) -> Logic, vector_op: impl Fn(&LogicVec, &LogicVec) -> LogicVec, table: [[Logic; 4]; 4]
+    ) {
+        for (i, &lhs) in ALL.iter().enumerate() {
+            for (j, &rhs) in ALL.iter().enumerate() {
+                assert_eq!(bit_op(lhs, rhs), table[i][j], "{} and {}", lhs, rhs);
+                assert_eq!(vector_op(&lhs.into(), &rhs.into()).get(0), table[i][j], "{} and {} as vectors", lhs, rhs);
+            }
+        }
+    }
+
+    fn assert_unused_bits_clear(vector: &LogicVec) {
+        let used_bits = vector.width % BITS_PER_WORD;
+        assert_eq!(vector.words.len(), vector.width.div_ceil(BITS_PER_WORD));
+        if used_bits != 0 {
+            let last_word = vector.words.last().unwrap();
+            assert_eq!(last_word.aval >> used_bits, 0, "aval past the width of {}", vector);
+            assert_eq!(last_word.bval >> used_bits, 0, "bval past the width of {}", vector);
+        }
+    }
+
+    #[test]
+    fn and_truth_table() {
+        use Logic::*;
+        check_truth_table(|l, r| l & r, |l, r| l & r, [
+            [Zero, Zero, Zero, Zero],
+            [Zero, One,  X,    X   ],
+            [Zero, X,    X,    X   ],
+            [Zero, X,    X,    X   ],
+        ]);
+    }
+
+    #[test]
+    fn or_truth_table() {
+        use Logic::*;
+        check_truth_table(|l, r| l | r, |l, r| l | r, [
+            [Zero, One,  X,    X   ],
+            [One,  One,  One,  One ],
+            [X,    One,  X,    X   ],
+            [X,    One,  X,    X   ],
+        ]);
+    }
+
+    #[test]
+    fn xor_truth_table() {
+        use Logic::*;
+        check_truth_table(|l, r| l ^ r, |l, r| l ^ r, [
+            [Zero, One,  X,    X   ],
+            [One,  Zero, X,    X   ],
+            [X,    X,    X,    X   ],
+            [X,    X,    X,    X   ],
+        ]);
+    }
+
+    #[test]
+    fn not_truth_table() {
+        let expected = [Logic::One, Logic::Zero, Logic::X, Logic::X];
+        for (&bit, &expected) in ALL.iter().zip(expected.iter()) {
+            assert_eq!(!bit, expected);
+            assert_eq!((!LogicVec::from(bit)).get(0), expected);
+        }
+
+        let vector: LogicVec = "10xz".parse().unwrap();
+        assert_eq!((!&vector).to_string(), "01xx");
+        assert_unused_bits_clear(&!&vector);
+    }
+
+    #[test]
+    fn operators_zero_extend_the_narrower_operand() {
+        let wide: LogicVec = "1111_1111".parse().unwrap();
+        let narrow: LogicVec = "x1".parse().unwrap();
+        assert_eq!((&wide & &narrow).to_string(), "000000x1");
+        assert_eq!((&wide | &narrow).to_string(), "11111111");
+        assert_eq!((&narrow ^ &wide).to_string(), "111111x0");
+    }
+
+    #[test]
+    fn unused_bits_stay_clear() {
+        let all_x = LogicVec::filled(40, Logic::X);
+        assert_unused_bits_clear(&all_x);
+
+        let truncated = all_x.resized(4);
+        assert_unused_bits_clear(&truncated);
+        assert_eq!(truncated, LogicVec::filled(4, Logic::X));
+
+        let extended = LogicVec::filled(4, Logic::Z).resized(36);
+        assert_unused_bits_clear(&extended);
+        assert_eq!(extended.to_string(), format!("{}zzzz", "0".repeat(32)));
+
+        let sliced = all_x.slice(3..38);
+        assert_unused_bits_clear(&sliced);
+        assert_eq!(sliced, LogicVec::filled(35, Logic::X));
+
+        let concatenated = LogicVec::filled(5, Logic::One).concat(&LogicVec::filled(30, Logic::Z));
+        assert_unused_bits_clear(&concatenated);
+        assert_eq!(concatenated.to_string(), format!("{}{}", "1".repeat(5), "z".repeat(30)));
+
+        assert_unused_bits_clear(&!&LogicVec::zeros(33));
+        assert_unused_bits_clear(&LogicVec::from_u128(u128::MAX, 70));
+    }
+
+    #[test]
+    fn slice_and_concat() {
+        let vector: LogicVec = "1100_xz10".parse().unwrap();
+        assert_eq!(vector.slice(4..8).to_string(), "1100");
+        assert_eq!(vector.slice(..=3).to_string(), "xz10");
+        assert_eq!(vector.slice(2..2).width(), 0);
+        assert_eq!(vector.slice(4..).concat(&vector.slice(..4)), vector);
+    }
+
+    #[test]
+    fn integer_conversions() {
+        let vector = LogicVec::from_u64(0xdead_beef, 32);
+        assert_eq!(u64::try_from(&vector).unwrap(), 0xdead_beef);
+        assert_eq!(LogicVec::from_u64(0x1ff, 8), LogicVec::from_u64(0xff, 8));
+
+        let wide = LogicVec::from_u128(u128::MAX, 128);
+        assert_eq!(u128::try_from(&wide).unwrap(), u128::MAX);
+        assert!(u64::try_from(&wide).is_err());
+        assert_eq!(u64::try_from(&wide.resized(200)).ok(), None);
+        assert_eq!(u128::try_from(&wide.resized(200)).unwrap(), u128::MAX);
+
+        let mut unknown = LogicVec::from_u64(1, 8);
+        unknown.set(5, Logic::Z);
+        assert!(u64::try_from(&unknown).is_err());
+
+        assert!(bool::try_from(&LogicVec::from(true)).unwrap());
+        assert!(bool::try_from(&LogicVec::from_u64(1, 2)).is_err());
+        assert!(bool::try_from(&LogicVec::from(Logic::X)).is_err());
+    }
+
+    #[test]
+    fn byte_conversions() {
+        let bytes = [0x01, 0x23, 0x45, 0x67, 0x89];
+        let vector = LogicVec::from_bytes(&bytes);
+        assert_eq!(vector.width(), 40);
+        assert_eq!(u64::try_from(&vector).unwrap(), 0x89_6745_2301);
+        assert_eq!(vector.to_bytes().unwrap(), bytes);
+
+        //Partial bytes are zero-extended
+        assert_eq!(LogicVec::from_u64(0x1ff, 9).to_bytes().unwrap(), vec![0xff, 0x01]);
+
+        assert!(LogicVec::filled(8, Logic::X).to_bytes().is_err());
+    }
+
+    #[test]
+    fn strings() {
+        let vector: LogicVec = "10_xZ".parse().unwrap();
+        assert_eq!(vector.width(), 4);
+        assert_eq!(vector.get(0), Logic::Z);
+        assert_eq!(vector.get(1), Logic::X);
+        assert_eq!(vector.to_string(), "10xz");
+        assert!("102".parse::<LogicVec>().is_err());
+    }
+}
+
+/* ------------------------------------------------------------------------------------------------
+ * Benchmarks
+ * --------------------------------------------------------------------------------------------- */
+
+//TODO
@@ -0,0 +1,245 @@
+/*
+ * File:    monitor.rs
+ * Brief:   Subscribing to changes in the values of signals, including posedges and negedges.
+ *
+ * Copyright (C) 2023 John Jekel
+ * See the LICENSE file at the root of the project for licensing info.
+ *
+ * Built on cbValueChange callbacks. Each subscription keeps the last value it saw so that it can
+ * filter out callbacks where the value didn't actually change in the chosen format.
+ *
+*/
+
+/*!
+ * Subscribing to changes in the values of signals.
+ *
+ * [`ObjectHandle::on_change()`] calls a closure every time a signal's value changes, with the new
+ * value already decoded into whichever [`SignalValue`] type you asked for:
+ *
+ * ```ignore
+ * let handle = ObjectHandle::by_name("top.counter")?;
+ * handle.on_change(|event, value: &LogicVec| {
+ *     sim_println!("counter is now {} at {:?}", value, event.time());
+ * })?;
+ * ```
+ *
+ * [`ObjectHandle::on_change_filtered()`] does the same, but skips changes that don't pass a
+ * [`ChangeFilter`] (ex. to ignore values containing X or Z).
+ *
+ * For clocks and other edge-sensitive signals, [`on_posedge()`], [`on_negedge()`] and [`on_edge()`]
+ * call a closure on the same transitions `@(posedge clk)` and `@(negedge clk)` would wake up on
+ * (ex. 0 to X is a posedge, but X to Z isn't an edge at all). Like in SystemVerilog, only the least
+ * significant bit of a vector counts.
+ *
+*/
+
+/* ------------------------------------------------------------------------------------------------
+ * Uses
+ * --------------------------------------------------------------------------------------------- */
+
+use crate::ObjectHandle;
+use crate::callbacks::{CallbackBuilder, CallbackEvent, CallbackHandle, CallbackReason};
+use crate::logic::{Logic, LogicVec};
+use crate::result::Result;
+use crate::value::{BinStr, DecStr, GetValue, HexStr, OctStr, Value};
+
+/* ------------------------------------------------------------------------------------------------
+ * Macros
+ * --------------------------------------------------------------------------------------------- */
+
+///Implements SignalValue for a type that is stored in a variant of Value
+macro_rules! signal_value {
+    ($type:ty, $variant:ident, |$value:ident| $is_known:expr) => {
+        impl SignalValue for $type {
+            fn from_value(value: &Value) -> Option<$type> {
+                match value {
+                    Value::$variant(inner) => Some(inner.clone()),
+                    _ => None
+                }
+            }
+
+            fn is_known(&self) -> bool {
+                let $value = self;
+                $is_known
+            }
+        }
+    };
+}
+
+/* ------------------------------------------------------------------------------------------------
+ * Types
+ * --------------------------------------------------------------------------------------------- */
+
+///Which changes [`ObjectHandle::on_change_filtered()`] passes on to its closure
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ChangeFilter {
+    ///Skip changes where the decoded value is the same as the last one (ex. a vpiIntVal when only
+    ///X bits changed)
+    pub only_if_different: bool,
+    ///Skip changes to values containing X or Z
+    pub only_known: bool
+}
+
+///A transition of a signal as defined by `posedge` and `negedge` in IEEE 1800 (table 9-2)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Edge {
+    ///0 to 1, X or Z; or X or Z to 1
+    Posedge,
+    ///1 to 0, X or Z; or X or Z to 0
+    Negedge
+}
+
+/* ------------------------------------------------------------------------------------------------
+ * Associated Functions and Methods
+ * --------------------------------------------------------------------------------------------- */
+
+impl ObjectHandle {
+    ///Calls the closure with the new value every time the object's value changes
+    ///
+    ///The callback keeps the handle (the simulator needs it for as long as the callback is
+    ///registered) and stays registered until it is removed with the returned [`CallbackHandle`].
+    pub fn on_change<T: SignalValue>(self, func: impl FnMut(&CallbackEvent, &T) + 'static) -> Result<CallbackHandle> {
+        self.on_change_filtered(ChangeFilter::default(), func)
+    }
+
+    ///Like [`ObjectHandle::on_change()`], but only for changes that pass the filter
+    pub fn on_change_filtered<T: SignalValue>(
+        self, filter: ChangeFilter, mut func: impl FnMut(&CallbackEvent, &T) + 'static
+    ) -> Result<CallbackHandle> {
+        let mut last_value: Option<T> = self.get_value().ok();
+
+        CallbackBuilder::new()
+            .reason(CallbackReason::ValueChange)
+            .object(self)
+            .value_format::<T>()
+            .call(move |event| {
+                let Some(value) = event.value().and_then(T::from_value) else {
+                    return;
+                };
+
+                if filter.only_known && !value.is_known() {
+                    return;
+                }
+                if filter.only_if_different && last_value.as_ref() == Some(&value) {
+                    return;
+                }
+
+                func(event, &value);
+                last_value = Some(value);
+            })
+            .register()
+    }
+}
+
+impl Edge {
+    ///The edge a transition from one value to another is, if any (ex. X to Z is neither)
+    pub fn from_transition(from: Logic, to: Logic) -> Option<Edge> {
+        match (from, to) {
+            (Logic::Zero, Logic::One | Logic::X | Logic::Z) | (Logic::X | Logic::Z, Logic::One)  => Some(Edge::Posedge),
+            (Logic::One, Logic::Zero | Logic::X | Logic::Z) | (Logic::X | Logic::Z, Logic::Zero) => Some(Edge::Negedge),
+            _                                                                                   => None
+        }
+    }
+}
+
+/* ------------------------------------------------------------------------------------------------
+ * Traits And Default Implementations
+ * --------------------------------------------------------------------------------------------- */
+
+///A type [`ObjectHandle::on_change()`] can decode new values into
+pub trait SignalValue: GetValue + Clone + PartialEq + 'static {
+    ///Extracts the value from what the callback provided, if it is in this type's format
+    fn from_value(value: &Value) -> Option<Self>;
+
+    ///False if the value contains X or Z
+    fn is_known(&self) -> bool;
+}
+
+/* ------------------------------------------------------------------------------------------------
+ * Trait Implementations
+ * --------------------------------------------------------------------------------------------- */
+
+signal_value!(Logic, Scalar, |logic| !logic.is_unknown());
+signal_value!(LogicVec, Vector, |vector| !vector.has_unknown());
+signal_value!(i32, Int, |_integer| true);
+signal_value!(f64, Real, |_real| true);
+signal_value!(String, String, |_string| true);
+signal_value!(BinStr, BinStr, |string| is_known_str(&string.0));
+signal_value!(OctStr, OctStr, |string| is_known_str(&string.0));
+signal_value!(DecStr, DecStr, |string| is_known_str(&string.0));
+signal_value!(HexStr, HexStr, |string| is_known_str(&string.0));
+
+impl SignalValue for Value {
+    fn from_value(value: &Value) -> Option<Value> {
+        Some(value.clone())
+    }
+
+    fn is_known(&self) -> bool {
+        match self {
+            Value::Scalar(logic)        => logic.is_known(),
+            Value::Vector(vector)       => vector.is_known(),
+            Value::BinStr(string)       => string.is_known(),
+            Value::OctStr(string)       => string.is_known(),
+            Value::DecStr(string)       => string.is_known(),
+            Value::HexStr(string)       => string.is_known(),
+            Value::Strength(strengths)  => strengths.iter().all(|strength| strength.logic.is_known()),
+            _                           => true
+        }
+    }
+}
+
+/* ------------------------------------------------------------------------------------------------
+ * Functions
+ * --------------------------------------------------------------------------------------------- */
+
+///Calls the closure on every posedge of the signal (like `@(posedge signal)`)
+pub fn on_posedge(signal: impl Into<ObjectHandle>, mut func: impl FnMut(&CallbackEvent) + 'static) -> Result<CallbackHandle> {
+    on_edge(signal, move |event, edge| {
+        if edge == Edge::Posedge {
+            func(event);
+        }
+    })
+}
+
+///Calls the closure on every negedge of the signal (like `@(negedge signal)`)
+pub fn on_negedge(signal: impl Into<ObjectHandle>, mut func: impl FnMut(&CallbackEvent) + 'static) -> Result<CallbackHandle> {
+    on_edge(signal, move |event, edge| {
+        if edge == Edge::Negedge {
+            func(event);
+        }
+    })
+}
+
+///Calls the closure on every posedge and negedge of the signal (like `@(edge signal)`)
+pub fn on_edge(signal: impl Into<ObjectHandle>, mut func: impl FnMut(&CallbackEvent, Edge) + 'static) -> Result<CallbackHandle> {
+    let signal = signal.into();
+
+    //Vector values work for scalars too, and for vectors only the least significant bit counts
+    let least_significant_bit = |vector: &LogicVec| if vector.width() > 0 { vector.get(0) } else { Logic::X };
+    let mut previous = signal.get_value::<LogicVec>().map_or(Logic::X, |vector| least_significant_bit(&vector));
+
+    signal.on_change(move |event, vector: &LogicVec| {
+        let current = least_significant_bit(vector);
+        if let Some(edge) = Edge::from_transition(previous, current) {
+            func(event, edge);
+        }
+        previous = current;
+    })
+}
+
+///The simulator uses x/X and z/Z (and sometimes ? for a partially unknown digit) in value strings
+fn is_known_str(string: &str) -> bool {
+    !string.chars().any(|c| matches!(c, 'x' | 'X' | 'z' | 'Z' | '?'))
+}
+
+/* ------------------------------------------------------------------------------------------------
+ * Tests
+ * --------------------------------------------------------------------------------------------- */
+
+//TODO
+
+/* ------------------------------------------------------------------------------------------------
+ * Benchmarks
+ * --------------------------------------------------------------------------------------------- */
+
+//TODO
@@ -0,0 +1,494 @@
+/*
+ * File:    objects.rs
+ * Brief:   Strongly typed wrappers around ObjectHandle.
+ *
+ * Copyright (C) 2023 John Jekel
+ * See the LICENSE file at the root of the project for licensing info.
+ *
+ * Each wrapper only exposes the properties and relationships the IEEE 1800 object model allows for
+ * its class of objects.
+ *
+*/
+
+/*!
+ * Strongly typed wrappers around [`ObjectHandle`].
+ *
+ * A plain [`ObjectHandle`] lets you ask any object for any property, and the simulator will simply
+ * hand back nothing if the object model doesn't allow it. The wrappers here ([`Module`], [`Net`],
+ * [`Variable`], [`Port`], [`Parameter`] and [`Scope`]) instead only expose what is legal for their
+ * class of objects, turning those mistakes into compile errors.
+ *
+ * Convert an [`ObjectHandle`] into a wrapper with [`TryFrom`], which checks the object's `vpiType`.
+ * You can always get back to the underlying [`ObjectHandle`] with `as_handle()` or `into_handle()`.
+ *
+*/
+
+/* ------------------------------------------------------------------------------------------------
+ * Uses
+ * --------------------------------------------------------------------------------------------- */
+
+use crate::{ManyRelationship, ObjectHandle, ObjectIterator, ObjectType, Property, Relationship};
+use crate::result::{self, Error, Result};
+use crate::startup::{panic_if_in_startup_routine, panic_if_not_main_thread};
+
+/* ------------------------------------------------------------------------------------------------
+ * Macros
+ * --------------------------------------------------------------------------------------------- */
+
+///Defines a wrapper around ObjectHandle that can only be created from the listed object types
+macro_rules! typed_handle {
+    ($(#[$wrapper_attr:meta])* $wrapper:ident: [$($(#[$attr:meta])* $object_type:ident),* $(,)?]) => {
+        $(#[$wrapper_attr])*
+        #[derive(Debug, PartialEq, Eq, Hash)]
+        #[repr(transparent)]
+        pub struct $wrapper {
+            handle: ObjectHandle
+        }
+
+        impl $wrapper {
+            pub fn as_handle(&self) -> &ObjectHandle {
+                &self.handle
+            }
+
+            pub fn into_handle(self) -> ObjectHandle {
+                self.handle
+            }
+
+            pub fn name(&self) -> Result<String> {
+                self.handle.name()
+            }
+
+            pub fn full_name(&self) -> Result<String> {
+                self.handle.full_name()
+            }
+
+            pub fn object_type(&self) -> Result<ObjectType> {
+                self.handle.object_type()
+            }
+
+            ///Returns true if an object of the given type can be wrapped by this
+            pub fn accepts(object_type: ObjectType) -> bool {
+                match object_type {
+                    $($(#[$attr])* ObjectType::$object_type => true,)*
+                    _ => false
+                }
+            }
+        }
+
+        impl TryFrom<ObjectHandle> for $wrapper {
+            type Error = Box<Error>;
+
+            fn try_from(handle: ObjectHandle) -> Result<$wrapper> {
+                let actual = handle.object_type()?;
+                if $wrapper::accepts(actual) {
+                    Ok($wrapper { handle })
+                } else {
+                    Err(Box::new(Error::WrongObjectType { expected: stringify!($wrapper), actual }))
+                }
+            }
+        }
+
+        impl From<$wrapper> for ObjectHandle {
+            fn from(wrapper: $wrapper) -> ObjectHandle {
+                wrapper.handle
+            }
+        }
+
+        impl AsRef<ObjectHandle> for $wrapper {
+            fn as_ref(&self) -> &ObjectHandle {
+                &self.handle
+            }
+        }
+    };
+}
+
+/* ------------------------------------------------------------------------------------------------
+ * Types
+ * --------------------------------------------------------------------------------------------- */
+
+typed_handle! {
+    ///A module instance
+    Module: [Module]
+}
+
+typed_handle! {
+    ///A net (wire, tri, etc.) or a bit of one
+    Net: [
+        Net,
+        NetBit,
+        #[cfg(feature = "ieee1800-2005")] EnumNet,
+        #[cfg(feature = "ieee1800-2005")] IntegerNet,
+        #[cfg(feature = "ieee1800-2005")] TimeNet,
+        #[cfg(feature = "ieee1800-2005")] StructNet,
+        #[cfg(feature = "ieee1800-2009")] PackedArrayNet,
+    ]
+}
+
+typed_handle! {
+    ///A variable (reg, integer, logic, etc.) or a bit of one
+    Variable: [
+        Reg,
+        RegBit,
+        IntegerVar,
+        RealVar,
+        TimeVar,
+        #[cfg(feature = "ieee1800-2005")] LongIntVar,
+        #[cfg(feature = "ieee1800-2005")] ShortIntVar,
+        #[cfg(feature = "ieee1800-2005")] IntVar,
+        #[cfg(feature = "ieee1800-2005")] ShortRealVar,
+        #[cfg(feature = "ieee1800-2005")] ByteVar,
+        #[cfg(feature = "ieee1800-2005")] ClassVar,
+        #[cfg(feature = "ieee1800-2005")] StringVar,
+        #[cfg(feature = "ieee1800-2005")] EnumVar,
+        #[cfg(feature = "ieee1800-2005")] StructVar,
+        #[cfg(feature = "ieee1800-2005")] UnionVar,
+        #[cfg(feature = "ieee1800-2005")] BitVar,
+        #[cfg(feature = "ieee1800-2005")] ChandleVar,
+        #[cfg(feature = "ieee1800-2009")] PackedArrayVar,
+    ]
+}
+
+typed_handle! {
+    ///A port of a module (or a bit of one)
+    Port: [Port, PortBit]
+}
+
+typed_handle! {
+    ///A parameter, localparam, or specparam
+    Parameter: [Parameter, SpecParam]
+}
+
+typed_handle! {
+    ///Anything that can contain declarations: modules, named blocks, tasks, functions, etc.
+    Scope: [
+        Module,
+        NamedBegin,
+        NamedFork,
+        Task,
+        Function,
+        GenScope,
+        #[cfg(feature = "ieee1800-2005")] Interface,
+        #[cfg(feature = "ieee1800-2005")] Program,
+        #[cfg(feature = "ieee1800-2005")] Package,
+        #[cfg(feature = "ieee1800-2005")] ClassDefn,
+        #[cfg(feature = "ieee1800-2005")] ClockingBlock,
+    ]
+}
+
+typed_handle! {
+    ///An event scheduled by a delayed [`ObjectHandle::put_value()`]
+    ScheduledEvent: [SchedEvent]
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(i32)]
+pub enum Direction {
+    Input = sv_bindings::vpiInput,
+    Output = sv_bindings::vpiOutput,
+    Inout = sv_bindings::vpiInout,
+    MixedIO = sv_bindings::vpiMixedIO,
+    NoDirection = sv_bindings::vpiNoDirection,
+    #[cfg(feature = "ieee1800-2005")]
+    Ref = sv_bindings::vpiRef
+}
+
+/* ------------------------------------------------------------------------------------------------
+ * Associated Functions and Methods
+ * --------------------------------------------------------------------------------------------- */
+
+impl Module {
+    pub fn def_name(&self) -> Result<String> {
+        self.handle.def_name()
+    }
+
+    pub fn is_top_module(&self) -> Result<bool> {
+        self.handle.get_bool(Property::TopModule)
+    }
+
+    pub fn is_cell_instance(&self) -> Result<bool> {
+        self.handle.get_bool(Property::CellInstance)
+    }
+
+    pub fn def_file(&self) -> Result<String> {
+        self.handle.get_str(Property::DefFile)
+    }
+
+    ///The module this one is instantiated in (fails for top-level modules)
+    pub fn parent(&self) -> Result<Module> {
+        self.handle.related(Relationship::Module)?.try_into()
+    }
+
+    pub fn submodules(&self) -> impl Iterator<Item = Module> {
+        children(&self.handle, ObjectType::Module)
+    }
+
+    pub fn ports(&self) -> impl Iterator<Item = Port> {
+        children(&self.handle, ObjectType::Port)
+    }
+
+    pub fn nets(&self) -> impl Iterator<Item = Net> {
+        children(&self.handle, ObjectType::Net)
+    }
+
+    pub fn regs(&self) -> impl Iterator<Item = Variable> {
+        children(&self.handle, ObjectType::Reg)
+    }
+
+    ///All variables in the module, not just regs
+    pub fn variables(&self) -> impl Iterator<Item = Variable> {
+        variables(&self.handle)
+    }
+
+    pub fn parameters(&self) -> impl Iterator<Item = Parameter> {
+        children(&self.handle, ObjectType::Parameter)
+    }
+}
+
+impl Net {
+    pub fn size(&self) -> Result<usize> {
+        self.handle.size()
+    }
+
+    pub fn is_signed(&self) -> Result<bool> {
+        self.handle.is_signed()
+    }
+
+    pub fn is_scalar(&self) -> Result<bool> {
+        self.handle.get_bool(Property::Scalar)
+    }
+
+    pub fn is_vector(&self) -> Result<bool> {
+        self.handle.is_vector()
+    }
+
+    pub fn is_implicitly_declared(&self) -> Result<bool> {
+        self.handle.get_bool(Property::ImplicitDecl)
+    }
+
+    pub fn module(&self) -> Result<Module> {
+        self.handle.related(Relationship::Module)?.try_into()
+    }
+}
+
+impl Variable {
+    pub fn size(&self) -> Result<usize> {
+        self.handle.size()
+    }
+
+    pub fn is_signed(&self) -> Result<bool> {
+        self.handle.is_signed()
+    }
+
+    pub fn is_scalar(&self) -> Result<bool> {
+        self.handle.get_bool(Property::Scalar)
+    }
+
+    pub fn is_vector(&self) -> Result<bool> {
+        self.handle.is_vector()
+    }
+
+    pub fn is_automatic(&self) -> Result<bool> {
+        self.handle.get_bool(Property::Automatic)
+    }
+
+    pub fn module(&self) -> Result<Module> {
+        self.handle.related(Relationship::Module)?.try_into()
+    }
+
+    pub fn scope(&self) -> Result<Scope> {
+        self.handle.related(Relationship::Scope)?.try_into()
+    }
+}
+
+impl Port {
+    pub fn size(&self) -> Result<usize> {
+        self.handle.size()
+    }
+
+    ///Fails if the simulator gives a direction we don't recognize
+    pub fn direction(&self) -> Result<Direction> {
+        match self.handle.get(Property::Direction)? {
+            sv_bindings::vpiInput       => Ok(Direction::Input),
+            sv_bindings::vpiOutput      => Ok(Direction::Output),
+            sv_bindings::vpiInout       => Ok(Direction::Inout),
+            sv_bindings::vpiMixedIO     => Ok(Direction::MixedIO),
+            #[cfg(feature = "ieee1800-2005")]
+            sv_bindings::vpiRef         => Ok(Direction::Ref),
+            sv_bindings::vpiNoDirection => Ok(Direction::NoDirection),
+            _                           => Err(Box::new(Error::UndefinedProperty(Property::Direction)))
+        }
+    }
+
+    ///The index of the port in the module's port list, starting at 0
+    pub fn index(&self) -> Result<i32> {
+        self.handle.get(Property::PortIndex)
+    }
+
+    pub fn module(&self) -> Result<Module> {
+        self.handle.related(Relationship::Module)?.try_into()
+    }
+
+    ///What the port is connected to in the instantiating module
+    pub fn high_conn(&self) -> Result<ObjectHandle> {
+        self.handle.related(Relationship::HighConn)
+    }
+
+    ///What the port is connected to inside the module itself
+    pub fn low_conn(&self) -> Result<ObjectHandle> {
+        self.handle.related(Relationship::LowConn)
+    }
+}
+
+impl Parameter {
+    pub fn size(&self) -> Result<usize> {
+        self.handle.size()
+    }
+
+    pub fn is_signed(&self) -> Result<bool> {
+        self.handle.is_signed()
+    }
+
+    pub fn is_local_param(&self) -> Result<bool> {
+        self.handle.get_bool(Property::LocalParam)
+    }
+
+    pub fn module(&self) -> Result<Module> {
+        self.handle.related(Relationship::Module)?.try_into()
+    }
+}
+
+impl ScheduledEvent {
+    ///Wraps the handle vpi_put_value() returns with vpiReturnEvent, which is always a vpiSchedEvent
+    ///
+    ///Skips the type check TryFrom does, since that can fail and we'd lose the only way to cancel
+    ///the write.
+    pub(crate) fn from_returned_event(handle: ObjectHandle) -> ScheduledEvent {
+        ScheduledEvent { handle }
+    }
+
+    ///True until the event has happened or been cancelled
+    pub fn is_scheduled(&self) -> Result<bool> {
+        self.handle.get_bool(Property::Scheduled)
+    }
+
+    ///Cancels the event so the value is never written (does nothing if it already happened)
+    pub fn cancel(&self) -> Result<()> {
+        panic_if_in_startup_routine!();
+        panic_if_not_main_thread!();
+
+        //Not done with put_value() since it looks up vpiSize, which scheduled events don't have
+        //SAFETY: We're calling vpi_put_value() from the main thread after startup routines have
+        //finished, the handle is guaranteed to be valid and non-null, and vpiCancelEvent takes
+        //neither a value nor a delay
+        unsafe {
+            sv_bindings::vpi_put_value(self.handle.handle.as_ptr(), std::ptr::null_mut(), std::ptr::null_mut(), sv_bindings::vpiCancelEvent)
+        };
+
+        match result::last_vpi_error() {
+            Some(error) => Err(error),
+            None        => Ok(())
+        }
+    }
+}
+
+impl Scope {
+    ///The scope this one is contained in (fails for top-level modules)
+    pub fn parent(&self) -> Result<Scope> {
+        self.handle.related(Relationship::Scope)?.try_into()
+    }
+
+    ///The instances (modules, interfaces, programs) and blocks (named blocks, generate scopes)
+    ///directly contained in this one. Tasks and functions are not included.
+    pub fn internal_scopes(&self) -> impl Iterator<Item = Scope> {
+        let instances = children(&self.handle, ObjectType::Module);
+        #[cfg(feature = "ieee1800-2005")]
+        let instances = instances
+            .chain(children(&self.handle, ObjectType::Interface))
+            .chain(children(&self.handle, ObjectType::Program));
+
+        //Some simulators also return instances here, so only keep blocks to avoid duplicates
+        let blocks = self.handle.related_iter(ManyRelationship::InternalScope).filter_map(|handle| {
+            match handle.object_type() {
+                Ok(ObjectType::NamedBegin | ObjectType::NamedFork | ObjectType::GenScope) => Some(Scope { handle }),
+                _ => None
+            }
+        });
+
+        instances.chain(blocks)
+    }
+
+    pub fn nets(&self) -> impl Iterator<Item = Net> {
+        children(&self.handle, ObjectType::Net)
+    }
+
+    pub fn regs(&self) -> impl Iterator<Item = Variable> {
+        children(&self.handle, ObjectType::Reg)
+    }
+
+    ///All variables in the scope, not just regs
+    pub fn variables(&self) -> impl Iterator<Item = Variable> {
+        variables(&self.handle)
+    }
+
+    pub fn parameters(&self) -> impl Iterator<Item = Parameter> {
+        children(&self.handle, ObjectType::Parameter)
+    }
+
+    ///Looks up an object by its name relative to this scope
+    pub fn child_by_name(&self, name: &str) -> Result<ObjectHandle> {
+        self.handle.child_by_name(name)
+    }
+}
+
+/* ------------------------------------------------------------------------------------------------
+ * Trait Implementations
+ * --------------------------------------------------------------------------------------------- */
+
+///Every module is also a scope
+impl From<Module> for Scope {
+    fn from(module: Module) -> Scope {
+        Scope { handle: module.handle }
+    }
+}
+
+/* ------------------------------------------------------------------------------------------------
+ * Functions
+ * --------------------------------------------------------------------------------------------- */
+
+///Iterates over objects of a given type in the reference, skipping any that can't be wrapped
+fn children<T: TryFrom<ObjectHandle>>(reference: &ObjectHandle, object_type: ObjectType) -> impl Iterator<Item = T> {
+    ObjectIterator::new_with_reference(object_type, reference).filter_map(|handle| T::try_from(handle).ok())
+}
+
+///Iterates over all variables in the reference, falling back to iterating over each Verilog
+///variable type for older simulators that don't support vpiVariables
+fn variables(reference: &ObjectHandle) -> impl Iterator<Item = Variable> {
+    let mut all_variables = reference.related_iter(ManyRelationship::Variables)
+        .filter_map(|handle| Variable::try_from(handle).ok())
+        .peekable();
+
+    let fallback: Box<dyn Iterator<Item = Variable>> = if all_variables.peek().is_none() {
+        Box::new(
+            children(reference, ObjectType::Reg)
+                .chain(children(reference, ObjectType::IntegerVar))
+                .chain(children(reference, ObjectType::RealVar))
+                .chain(children(reference, ObjectType::TimeVar))
+        )
+    } else {
+        Box::new(std::iter::empty())
+    };
+
+    all_variables.chain(fallback)
+}
+
+/* ------------------------------------------------------------------------------------------------
+ * Tests
+ * --------------------------------------------------------------------------------------------- */
+
+//TODO
+
+/* ------------------------------------------------------------------------------------------------
+ * Benchmarks
+ * --------------------------------------------------------------------------------------------- */
+
+//TODO
@@ -52,14 +52,58 @@ use std::fmt::Display;
 #[non_exhaustive]
 pub enum Error {
     Unknown,
-    //TODO others
-    Reason {
-        //TODO populate with what the standard provides through vpi_chk_error
-        todo: String,
+    UndefinedProperty(crate::Property),//The property doesn't apply to the object
+    InvalidObjectType(i32),//The simulator gave us a type we don't know about
+    InvalidObjectTypeName(String),//A string that doesn't name an object type
+    WrongObjectType {//The object can't be wrapped by the typed handle we tried to convert it to
+        expected: &'static str,
+        actual: crate::ObjectType
+    },
+    NameNotFound(String),//Nothing in the design hierarchy has this name
+    NoRelatedObject(crate::Relationship),//The object has nothing on the other end of the relationship
+    UnknownLogicValue,//A 4-state value with X or Z bits was converted to a 2-state one
+    ValueOutOfRange,//A value doesn't fit in the type it was converted to
+    InvalidLogicString(String),//A string that isn't made up of 0, 1, x and z
+    UnsupportedValueFormat(i32),//The simulator couldn't provide the value in this format
+    WrongValueWidth {//The value doesn't have exactly as many bits as the object it was written to
+        expected: usize,
+        actual: usize
+    },
+    WrongTimeType(i32),//A time that isn't a vpiSimTime was converted to a SimTime
+    InvalidTimeUnit(i32),//The exponent doesn't correspond to a time unit
+    CallbackNotRegistered(crate::callbacks::CallbackReason),//The simulator refused to register the callback
+    CallbackNotRemoved,//The simulator refused to remove the callback
+    NonAdvancingPeriod(crate::callbacks::Time),//A periodic timer would fire at the same time forever
+    Reason {//What the standard provides through vpi_chk_error()
+        state: ErrorState,
+        level: ErrorLevel,
+        message: String,
+        product: String,
+        code: String,
+        file: String,
+        line: i32
     },
     Other(Box<dyn std::error::Error>)//A non sv-api error
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(i32)]
+pub enum ErrorState {
+    Compile = sv_bindings::vpiCompile,
+    PLI = sv_bindings::vpiPLI,
+    Run = sv_bindings::vpiRun
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(i32)]
+pub enum ErrorLevel {
+    Notice = sv_bindings::vpiNotice,
+    Warning = sv_bindings::vpiWarning,
+    Error = sv_bindings::vpiError,
+    System = sv_bindings::vpiSystem,
+    Internal = sv_bindings::vpiInternal
+}
+
 pub type Result<T> = std::result::Result<T, Box<Error>>;
 
 /* ------------------------------------------------------------------------------------------------
@@ -90,9 +134,27 @@ impl std::error::Error for Error {
 impl Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Error::Unknown                  => write!(f, "Unknown or unclassified error"),
-            Error::Reason { todo }          => write!(f, "Reason: {}", todo),//TODO display this properly
-            Error::Other(other_boxed_error) => write!(f, "Other: {}", other_boxed_error)
+            Error::Unknown                          => write!(f, "Unknown or unclassified error"),
+            Error::UndefinedProperty(property)      => write!(f, "Property {:?} is undefined for this object", property),
+            Error::InvalidObjectType(raw)           => write!(f, "Invalid object type {}", raw),
+            Error::InvalidObjectTypeName(name)      => write!(f, "\"{}\" is not an object type", name),
+            Error::WrongObjectType { expected, actual } => write!(f, "Expected a {}, got a {}", expected, actual),
+            Error::NameNotFound(name)               => write!(f, "No object named \"{}\" was found", name),
+            Error::NoRelatedObject(relationship)    => write!(f, "No object is related by {:?}", relationship),
+            Error::UnknownLogicValue                => write!(f, "The value contains X or Z bits"),
+            Error::ValueOutOfRange                  => write!(f, "The value is out of range for the type it was converted to"),
+            Error::InvalidLogicString(string)       => write!(f, "\"{}\" is not a 4-state logic value", string),
+            Error::UnsupportedValueFormat(format)   => write!(f, "The value couldn't be provided in format {}", format),
+            Error::WrongValueWidth { expected, actual } => write!(f, "Expected a value {} bits wide, got {} bits", expected, actual),
+            Error::WrongTimeType(time_type)         => write!(f, "Expected a vpiSimTime, got time type {}", time_type),
+            Error::InvalidTimeUnit(exponent)        => write!(f, "No time unit is 10^{} seconds", exponent),
+            Error::CallbackNotRegistered(reason)    => write!(f, "The simulator refused to register a {:?} callback", reason),
+            Error::CallbackNotRemoved               => write!(f, "The simulator refused to remove the callback"),
+            Error::NonAdvancingPeriod(period)       => write!(f, "A period of {:?} doesn't advance simulation time", period),
+            Error::Reason { state, level, message, product, code, file, line } => write!(
+                f, "{:?} during {:?} from {} ({}) at {}:{}: {}", level, state, product, code, file, line, message
+            ),
+            Error::Other(other_boxed_error)         => write!(f, "Other: {}", other_boxed_error)
         }
     }
 }
@@ -101,7 +163,64 @@ impl Display for Error {
  * Functions
  * --------------------------------------------------------------------------------------------- */
 
-//TODO
+///Returns the error (if any) caused by the most recent VPI call
+///
+///Notices and warnings are ignored, since the call they came from still succeeded.
+///
+///Only call this after startup routines have finished and from the main thread
+pub(crate) fn last_vpi_error() -> Option<Box<Error>> {
+    let mut raw_error_info = sv_bindings::t_vpi_error_info {
+        state: 0,
+        level: 0,
+        message: std::ptr::null_mut(),
+        product: std::ptr::null_mut(),
+        code: std::ptr::null_mut(),
+        file: std::ptr::null_mut(),
+        line: 0
+    };
+
+    //SAFETY: The caller is responsible for only calling this from the main thread after startup
+    //routines have finished, and raw_error_info is a valid struct for the simulator to fill in
+    if unsafe { sv_bindings::vpi_chk_error(&mut raw_error_info) } == 0 {
+        return None;
+    }
+
+    let level = match raw_error_info.level {
+        sv_bindings::vpiNotice  => ErrorLevel::Notice,
+        sv_bindings::vpiWarning => ErrorLevel::Warning,
+        sv_bindings::vpiError   => ErrorLevel::Error,
+        sv_bindings::vpiSystem  => ErrorLevel::System,
+        _                       => ErrorLevel::Internal//Anything we don't recognize is likely bad
+    };
+
+    if level < ErrorLevel::Error {
+        return None;
+    }
+
+    //The strings belong to the simulator and may be overwritten by the next call, so copy them now
+    let copy_str = |ptr: *mut sv_bindings::PLI_BYTE8| if ptr.is_null() {
+        String::new()
+    } else {
+        //SAFETY: We should have been given a valid null-terminated string
+        unsafe { std::ffi::CStr::from_ptr(ptr) }.to_string_lossy().into_owned()
+    };
+
+    let state = match raw_error_info.state {
+        sv_bindings::vpiCompile => ErrorState::Compile,
+        sv_bindings::vpiPLI     => ErrorState::PLI,
+        _                       => ErrorState::Run
+    };
+
+    Some(Box::new(Error::Reason {
+        state,
+        level,
+        message:    copy_str(raw_error_info.message),
+        product:    copy_str(raw_error_info.product),
+        code:       copy_str(raw_error_info.code),
+        file:       copy_str(raw_error_info.file),
+        line:       raw_error_info.line
+    }))
+}
 
 /* ------------------------------------------------------------------------------------------------
  * Tests
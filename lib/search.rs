@@ -0,0 +1,427 @@
+/*
+ * File:    search.rs
+ * Brief:   Finds signals in the design hierarchy by glob or regex patterns on their full names.
+ *
+ * Copyright (C) 2023 John Jekel
+ * See the LICENSE file at the root of the project for licensing info.
+ *
+ * Both kinds of pattern are matched one level of the hierarchy at a time while walking it, so any
+ * scope that can't possibly contain a match is skipped without looking inside it.
+ *
+*/
+
+/*!
+ * Finds signals (nets and variables) in the design hierarchy by patterns on their full names.
+ *
+ * [`find_signals()`] takes a glob pattern such as `"top.*.u_core*.pc_*"`. Each `.`-separated
+ * component of the pattern is matched against the name of one level of the hierarchy, where `*`
+ * matches any number of characters and `?` matches exactly one. A component that is just `**` matches
+ * any number of levels (including none), so `"top.**.valid"` finds every signal named `valid` under
+ * `top`.
+ *
+ * With the `regex` feature enabled, [`find_signals_regex()`] instead takes a regular expression that
+ * must match the entire full name of the signal (as if it were surrounded by `^` and `$`).
+ *
+ * In both cases, scopes whose names already rule out a match are not descended into, which matters on
+ * large designs.
+ *
+*/
+
+/* ------------------------------------------------------------------------------------------------
+ * Uses
+ * --------------------------------------------------------------------------------------------- */
+
+use crate::ObjectHandle;
+use crate::hierarchy::{self, Order, Visitor};
+use crate::objects::{Net, Scope, Variable};
+use crate::result::Result;
+#[cfg(feature = "regex")]
+use crate::result::Error;
+
+#[cfg(feature = "regex")]
+use regex_automata::{Anchored, dfa::Automaton, dfa::dense::DFA, util::primitives::StateID, util::start};
+
+/* ------------------------------------------------------------------------------------------------
+ * Types
+ * --------------------------------------------------------------------------------------------- */
+
+///A glob pattern split into `.`-separated components, matched one level of the hierarchy at a time
+///
+///A state is the index of the next component to match, and a set of them is kept since `**` can
+///match any number of levels.
+struct GlobPattern<'a> {
+    components: Vec<&'a str>
+}
+
+///Matches a glob pattern against the hierarchy one level at a time
+struct GlobVisitor<'a> {
+    pattern: GlobPattern<'a>,
+    //For each scope we're inside of, which pattern components the next level could be matched by
+    state_stack: Vec<Vec<usize>>,
+    matches: Vec<ObjectHandle>
+}
+
+///A regex compiled to a DFA that full names are fed into one level of the hierarchy at a time
+#[cfg(feature = "regex")]
+struct RegexPattern {
+    dfa: DFA<Vec<u32>>
+}
+
+///Matches a regex against the hierarchy by feeding each level's name into a DFA
+#[cfg(feature = "regex")]
+struct RegexVisitor {
+    pattern: RegexPattern,
+    //For each scope we're inside of, the DFA state after its full name and the following "."
+    state_stack: Vec<StateID>,
+    matches: Vec<ObjectHandle>
+}
+
+/* ------------------------------------------------------------------------------------------------
+ * Associated Functions and Methods
+ * --------------------------------------------------------------------------------------------- */
+
+impl<'a> GlobPattern<'a> {
+    fn new(pattern: &'a str) -> GlobPattern<'a> {
+        GlobPattern {
+            components: pattern.split('.').collect()
+        }
+    }
+
+    ///The states before the top level of the hierarchy
+    fn initial_states(&self) -> Vec<usize> {
+        self.skip_double_stars(vec![0])
+    }
+
+    ///Given the states before a level named `name`, returns the states after it
+    fn advance(&self, states: &[usize], name: &str) -> Vec<usize> {
+        let mut next_states = Vec::new();
+        for &state in states {
+            match self.components.get(state) {
+                Some(&"**") => next_states.push(state),//Consume this level and stay put
+                Some(component) if glob_matches(component, name) => next_states.push(state + 1),
+                _ => {}
+            }
+        }
+        self.skip_double_stars(next_states)
+    }
+
+    ///True if there are components left to match anything inside a scope with
+    fn can_match_inside(&self, states: &[usize]) -> bool {
+        states.iter().any(|&state| state < self.components.len())
+    }
+
+    ///True if every component has been matched
+    fn is_match(&self, states: &[usize]) -> bool {
+        states.contains(&self.components.len())
+    }
+
+    ///Adds the states reachable by having a "**" match zero levels
+    fn skip_double_stars(&self, mut states: Vec<usize>) -> Vec<usize> {
+        let mut i = 0;
+        while i < states.len() {
+            let state = states[i];
+            if self.components.get(state) == Some(&"**") && !states.contains(&(state + 1)) {
+                states.push(state + 1);
+            }
+            i += 1;
+        }
+        states
+    }
+}
+
+impl<'a> GlobVisitor<'a> {
+    fn new(pattern: &'a str) -> GlobVisitor<'a> {
+        let pattern = GlobPattern::new(pattern);
+        let initial_states = pattern.initial_states();
+        GlobVisitor {
+            pattern,
+            state_stack: vec![initial_states],
+            matches: Vec::new()
+        }
+    }
+
+    fn visit_signal(&mut self, name: Result<String>, handle: ObjectHandle) {
+        let Ok(name) = name else {
+            return;
+        };
+        let states = self.state_stack.last().expect("There is always at least the initial state");
+        if self.pattern.is_match(&self.pattern.advance(states, &name)) {
+            self.matches.push(handle);
+        }
+    }
+}
+
+#[cfg(feature = "regex")]
+impl RegexPattern {
+    fn new(pattern: &str) -> Result<RegexPattern> {
+        let dfa = DFA::new(&format!(r"(?:{})\z", pattern)).map_err(|e| Box::new(Error::Other(Box::new(e))))?;
+        Ok(RegexPattern { dfa })
+    }
+
+    ///The state before the top level of the hierarchy
+    fn start_state(&self) -> Result<StateID> {
+        let config = start::Config::new().anchored(Anchored::Yes);
+        self.dfa.start_state(&config).map_err(|e| Box::new(Error::Other(Box::new(e))))
+    }
+
+    ///The state after a scope named `name` (and the following "."), or None if no full name inside
+    ///it can possibly match
+    fn enter_scope(&self, state: StateID, name: &str) -> Option<StateID> {
+        let next_state = self.feed(self.feed(state, name.as_bytes()), b".");
+        (!self.dfa.is_dead_state(next_state)).then_some(next_state)
+    }
+
+    ///True if a signal named `name` inside the scope the state is for matches
+    fn is_match(&self, state: StateID, name: &str) -> bool {
+        let end_state = self.dfa.next_eoi_state(self.feed(state, name.as_bytes()));
+        self.dfa.is_match_state(end_state)
+    }
+
+    fn feed(&self, mut state: StateID, bytes: &[u8]) -> StateID {
+        for &byte in bytes {
+            state = self.dfa.next_state(state, byte);
+        }
+        state
+    }
+}
+
+#[cfg(feature = "regex")]
+impl RegexVisitor {
+    fn new(pattern: &str) -> Result<RegexVisitor> {
+        let pattern = RegexPattern::new(pattern)?;
+        let start_state = pattern.start_state()?;
+        Ok(RegexVisitor {
+            pattern,
+            state_stack: vec![start_state],
+            matches: Vec::new()
+        })
+    }
+
+    fn visit_signal(&mut self, name: Result<String>, handle: ObjectHandle) {
+        let Ok(name) = name else {
+            return;
+        };
+        let state = *self.state_stack.last().expect("There is always at least the start state");
+        if self.pattern.is_match(state, &name) {
+            self.matches.push(handle);
+        }
+    }
+}
+
+/* ------------------------------------------------------------------------------------------------
+ * Trait Implementations
+ * --------------------------------------------------------------------------------------------- */
+
+impl Visitor for GlobVisitor<'_> {
+    fn enter_scope(&mut self, scope: &Scope) -> bool {
+        let Ok(name) = scope.name() else {
+            return false;
+        };
+        let states = self.state_stack.last().expect("There is always at least the initial state");
+        let next_states = self.pattern.advance(states, &name);
+
+        //Only descend if there are pattern components left to match signals inside the scope with
+        if self.pattern.can_match_inside(&next_states) {
+            self.state_stack.push(next_states);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn visit_net(&mut self, net: Net) {
+        self.visit_signal(net.name(), net.into_handle());
+    }
+
+    fn visit_variable(&mut self, variable: Variable) {
+        self.visit_signal(variable.name(), variable.into_handle());
+    }
+
+    fn leave_scope(&mut self, _scope: &Scope) {
+        self.state_stack.pop();
+    }
+}
+
+#[cfg(feature = "regex")]
+impl Visitor for RegexVisitor {
+    fn enter_scope(&mut self, scope: &Scope) -> bool {
+        let Ok(name) = scope.name() else {
+            return false;
+        };
+        let state = *self.state_stack.last().expect("There is always at least the start state");
+
+        //If the DFA is dead, no full name starting with this scope's can possibly match
+        match self.pattern.enter_scope(state, &name) {
+            Some(next_state) => {
+                self.state_stack.push(next_state);
+                true
+            },
+            None => false
+        }
+    }
+
+    fn visit_net(&mut self, net: Net) {
+        self.visit_signal(net.name(), net.into_handle());
+    }
+
+    fn visit_variable(&mut self, variable: Variable) {
+        self.visit_signal(variable.name(), variable.into_handle());
+    }
+
+    fn leave_scope(&mut self, _scope: &Scope) {
+        self.state_stack.pop();
+    }
+}
+
+/* ------------------------------------------------------------------------------------------------
+ * Functions
+ * --------------------------------------------------------------------------------------------- */
+
+///Finds every net and variable whose full name matches the glob pattern (ex. "top.*.u_core*.pc_*")
+pub fn find_signals(pattern: &str) -> Vec<ObjectHandle> {
+    let mut visitor = GlobVisitor::new(pattern);
+    hierarchy::walk(&mut visitor, Order::DepthFirst);
+    visitor.matches
+}
+
+///Finds every net and variable whose entire full name matches the regular expression
+#[cfg(feature = "regex")]
+pub fn find_signals_regex(pattern: &str) -> Result<Vec<ObjectHandle>> {
+    let mut visitor = RegexVisitor::new(pattern)?;
+    hierarchy::walk(&mut visitor, Order::DepthFirst);
+    Ok(visitor.matches)
+}
+
+///Matches a single name against a single glob component, where `*` matches any number of characters
+///and `?` matches exactly one
+fn glob_matches(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+
+    //Classic greedy wildcard matching, backtracking to the most recent `*` on a mismatch
+    let (mut p, mut n) = (0, 0);
+    let mut last_star: Option<(usize, usize)> = None;
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            last_star = Some((p, n));
+            p += 1;
+        } else if let Some((star_p, star_n)) = last_star {
+            p = star_p + 1;
+            n = star_n + 1;
+            last_star = Some((star_p, star_n + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/* ------------------------------------------------------------------------------------------------
+ * Tests
+ * --------------------------------------------------------------------------------------------- */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    ///Matches a glob pattern against a signal's full name the way GlobVisitor does, returning None
+    ///if one of the scopes would have been pruned
+    fn glob_path_matches(pattern: &str, full_name: &str) -> Option<bool> {
+        let pattern = GlobPattern::new(pattern);
+        let mut levels: Vec<&str> = full_name.split('.').collect();
+        let signal = levels.pop().expect("split() always returns at least one string");
+
+        let mut states = pattern.initial_states();
+        for scope in levels {
+            states = pattern.advance(&states, scope);
+            if !pattern.can_match_inside(&states) {
+                return None;
+            }
+        }
+        Some(pattern.is_match(&pattern.advance(&states, signal)))
+    }
+
+    #[test]
+    fn glob_components() {
+        let table = [
+            ("*",       "",         true),
+            ("*",       "clk",      true),
+            ("?",       "",         false),
+            ("?",       "a",        true),
+            ("?",       "ab",       false),
+            ("pc_*",    "pc_next",  true),
+            ("pc_*",    "pc",       false),
+            ("*_q",     "data_q",   true),
+            ("*_q",     "data_d",   false),
+            ("a*b*c",   "aXbYc",    true),
+            ("a*b*c",   "abcabd",   false),
+            ("a*c",     "abcbc",    true),
+            ("d??a",    "data",     true),
+            ("d??a",    "dta",      false),
+            ("**",      "x",        true),
+            ("",        "",         true),
+            ("",        "a",        false),
+            ("clk",     "clk",      true),
+            ("clk",     "clk2",     false),
+        ];
+        for (pattern, name, expected) in table {
+            assert_eq!(glob_matches(pattern, name), expected, "{:?} against {:?}", pattern, name);
+        }
+    }
+
+    #[test]
+    fn glob_paths() {
+        let table = [
+            ("top.*.valid",             "top.u_core.valid",         Some(true)),
+            ("top.*.valid",             "top.u_core.ready",         Some(false)),
+            ("top.*.valid",             "bottom.u_core.valid",      None),
+            ("top.*.valid",             "top.a.b.valid",            None),
+            ("**.valid",                "valid",                    Some(true)),
+            ("**.valid",                "top.a.b.valid",            Some(true)),
+            ("**.valid",                "top.a.b.ready",            Some(false)),
+            ("top.**.valid",            "top.valid",                Some(true)),
+            ("top.**.valid",            "top.a.b.valid",            Some(true)),
+            ("top.**.valid",            "other.a.valid",            None),
+            ("top.**.u_*.pc_?",         "top.a.u_core.pc_q",        Some(true)),
+            ("top.**.u_*.pc_?",         "top.a.core.pc_q",          Some(false)),
+            ("top.**",                  "top.valid",                Some(true)),
+            ("top.**",                  "top.a.b.valid",            Some(true)),
+            ("top.**",                  "other.valid",              None),
+            ("**",                      "top.a.valid",              Some(true)),
+            ("top..valid",              "top..valid",               Some(true)),
+            ("top..valid",              "top.a.valid",              None),
+            ("top.a.",                  "top.a.valid",              Some(false)),
+        ];
+        for (pattern, full_name, expected) in table {
+            assert_eq!(glob_path_matches(pattern, full_name), expected, "{:?} against {:?}", pattern, full_name);
+        }
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn regex_prunes_through_dead_states() {
+        let pattern = RegexPattern::new(r"top\.u_core[0-9]+\.pc_.*").unwrap();
+        let start = pattern.start_state().unwrap();
+
+        let top = pattern.enter_scope(start, "top").expect("top can contain matches");
+        assert!(pattern.enter_scope(start, "bottom").is_none());
+        assert!(pattern.enter_scope(start, "topper").is_none());
+
+        let core = pattern.enter_scope(top, "u_core12").expect("u_core12 can contain matches");
+        assert!(pattern.enter_scope(top, "u_cache").is_none());
+
+        assert!(pattern.is_match(core, "pc_next"));
+        assert!(!pattern.is_match(core, "npc"));
+        assert!(!pattern.is_match(top, "pc_next"));
+    }
+}
+
+/* ------------------------------------------------------------------------------------------------
+ * Benchmarks
+ * --------------------------------------------------------------------------------------------- */
+
+//TODO
@@ -0,0 +1,413 @@
+/*
+ * File:    time.rs
+ * Brief:   Simulation time as a single 64-bit number, and the units it can be scaled to.
+ *
+ * Copyright (C) 2023 John Jekel
+ * See the LICENSE file at the root of the project for licensing info.
+ *
+ * s_vpi_time splits vpiSimTime into high and low 32-bit halves; SimTime joins them back together so
+ * that times can be compared and added like any other integer.
+ *
+*/
+
+/*!
+ * Simulation time.
+ *
+ * A [`SimTime`] is a number of ticks of the simulation's precision (the smallest time precision of
+ * any module in the design). It converts to and from `s_vpi_time` and [`callbacks::Time`], and supports
+ * ordinary comparisons and arithmetic.
+ *
+ * A [`TimeUnit`] is one of the units from a `` `timescale `` directive (ex. `10ns`). Each module has
+ * its own [`Timescale`] (see [`Module::timescale()`]), and delays written in that module are in its
+ * time unit. The precision of simulation time itself is [`simulation_precision()`]. Use
+ * [`SimTime::to_unit()`] and [`ScaledTime::to_sim_time()`] (or the lower-level
+ * [`SimTime::to_scaled()`] and [`SimTime::from_scaled()`]) to convert between them.
+ *
+ * The current time is available from [`sim_time()`] in ticks, or from [`Scope::local_time()`] as a
+ * [`ScaledTime`] in the time unit of the instance containing a scope, which displays nicely in log
+ * messages (ex. "1250 ns").
+ *
+*/
+
+/* ------------------------------------------------------------------------------------------------
+ * Uses
+ * --------------------------------------------------------------------------------------------- */
+
+use std::fmt;
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Sub, SubAssign};
+
+use crate::{ObjectHandle, ObjectType, Property, Relationship};
+use crate::callbacks::{self, TimeType};
+use crate::objects::{Module, Scope};
+use crate::result::{self, Error, Result};
+use crate::startup::{panic_if_in_startup_routine, panic_if_not_main_thread};
+
+/* ------------------------------------------------------------------------------------------------
+ * Types
+ * --------------------------------------------------------------------------------------------- */
+
+///A number of ticks of the simulation's precision
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SimTime(pub u64);
+
+///A unit of time, named like in a `` `timescale `` directive (ex. `Ns10` is `10ns`)
+///
+///The value of each is its exponent; for example `Ns1` is -9 since 1ns is 10^-9 s.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(i32)]
+pub enum TimeUnit {
+    Fs1 = -15,
+    Fs10 = -14,
+    Fs100 = -13,
+    Ps1 = -12,
+    Ps10 = -11,
+    Ps100 = -10,
+    Ns1 = -9,
+    Ns10 = -8,
+    Ns100 = -7,
+    Us1 = -6,
+    Us10 = -5,
+    Us100 = -4,
+    Ms1 = -3,
+    Ms10 = -2,
+    Ms100 = -1,
+    S1 = 0,
+    S10 = 1,
+    S100 = 2
+}
+
+///A time in some unit other than ticks of the simulation's precision
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ScaledTime {
+    ///The number of `unit`s (ex. 125 when the unit is 10ns and the time is 1250ns)
+    pub value: f64,
+    pub unit: TimeUnit
+}
+
+///The time unit and precision of a module, like a `` `timescale `` directive
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Timescale {
+    pub unit: TimeUnit,
+    pub precision: TimeUnit
+}
+
+/* ------------------------------------------------------------------------------------------------
+ * Associated Functions and Methods
+ * --------------------------------------------------------------------------------------------- */
+
+impl SimTime {
+    pub fn from_high_low(high: u32, low: u32) -> SimTime {
+        SimTime(((high as u64) << 32) | (low as u64))
+    }
+
+    pub fn high(self) -> u32 {
+        (self.0 >> 32) as u32
+    }
+
+    pub fn low(self) -> u32 {
+        self.0 as u32
+    }
+
+    ///Returns None instead of underflowing if `other` is later than `self`
+    pub fn checked_sub(self, other: SimTime) -> Option<SimTime> {
+        self.0.checked_sub(other.0).map(SimTime)
+    }
+
+    ///Converts to a (possibly fractional) number of `unit`s
+    ///
+    ///`simulation_precision` is the length of one tick of simulation time.
+    pub fn to_scaled(self, unit: TimeUnit, simulation_precision: TimeUnit) -> f64 {
+        self.0 as f64 * 10f64.powi(simulation_precision.exponent() - unit.exponent())
+    }
+
+    ///Converts to a number of `unit`s, using the simulation's precision
+    pub fn to_unit(self, unit: TimeUnit) -> Result<ScaledTime> {
+        Ok(ScaledTime { value: self.to_scaled(unit, simulation_precision()?), unit })
+    }
+
+    ///Converts from a number of `unit`s, rounding to the nearest tick of simulation time
+    ///
+    ///`simulation_precision` is the length of one tick of simulation time. Negative values become 0.
+    pub fn from_scaled(scaled: f64, unit: TimeUnit, simulation_precision: TimeUnit) -> SimTime {
+        SimTime((scaled * 10f64.powi(unit.exponent() - simulation_precision.exponent())).round() as u64)
+    }
+}
+
+impl TimeUnit {
+    ///The power of 10 (in seconds) of the unit
+    pub fn exponent(self) -> i32 {
+        self as i32
+    }
+
+    ///The unit with the given power of 10 (in seconds), if there is one
+    pub fn from_exponent(exponent: i32) -> Option<TimeUnit> {
+        const UNITS: [TimeUnit; 18] = [
+            TimeUnit::Fs1, TimeUnit::Fs10, TimeUnit::Fs100,
+            TimeUnit::Ps1, TimeUnit::Ps10, TimeUnit::Ps100,
+            TimeUnit::Ns1, TimeUnit::Ns10, TimeUnit::Ns100,
+            TimeUnit::Us1, TimeUnit::Us10, TimeUnit::Us100,
+            TimeUnit::Ms1, TimeUnit::Ms10, TimeUnit::Ms100,
+            TimeUnit::S1, TimeUnit::S10, TimeUnit::S100
+        ];
+        UNITS.into_iter().find(|unit| unit.exponent() == exponent)
+    }
+
+    ///The multiplier in front of the base unit (1, 10 or 100)
+    pub fn magnitude(self) -> u32 {
+        10u32.pow(self.exponent().rem_euclid(3) as u32)
+    }
+
+    ///The base unit's suffix ("fs", "ps", "ns", "us", "ms" or "s")
+    pub fn suffix(self) -> &'static str {
+        match self.exponent().div_euclid(3) {
+            -5  => "fs",
+            -4  => "ps",
+            -3  => "ns",
+            -2  => "us",
+            -1  => "ms",
+            _   => "s"
+        }
+    }
+}
+
+impl ScaledTime {
+    ///Converts to ticks of the simulation's precision, rounding to the nearest tick
+    pub fn to_sim_time(self) -> Result<SimTime> {
+        Ok(SimTime::from_scaled(self.value, self.unit, simulation_precision()?))
+    }
+}
+
+impl Module {
+    ///The time unit delays in the module are in (vpiTimeUnit)
+    pub fn time_unit(&self) -> Result<TimeUnit> {
+        self.as_handle().time_unit()
+    }
+
+    ///The precision delays in the module are rounded to (vpiTimePrecision)
+    pub fn time_precision(&self) -> Result<TimeUnit> {
+        self.as_handle().time_precision()
+    }
+
+    pub fn timescale(&self) -> Result<Timescale> {
+        Ok(Timescale {
+            unit: self.time_unit()?,
+            precision: self.time_precision()?
+        })
+    }
+}
+
+impl Scope {
+    ///The current simulation time in the time unit of the instance (module, interface, etc.) the
+    ///scope is in (using `vpi_get_time()`)
+    pub fn local_time(&self) -> Result<ScaledTime> {
+        //Only instances have a time unit, so blocks, tasks and functions use the enclosing one's
+        let enclosing_instance = match self.object_type()? {
+            ObjectType::Module => None,
+            #[cfg(feature = "ieee1800-2005")]
+            ObjectType::Interface | ObjectType::Program | ObjectType::Package => None,
+            #[cfg(feature = "ieee1800-2005")]
+            _ => Some(self.as_handle().related(Relationship::Instance)?),
+            #[cfg(not(feature = "ieee1800-2005"))]
+            _ => Some(self.as_handle().related(Relationship::Module)?)
+        };
+        let instance = enclosing_instance.as_ref().unwrap_or(self.as_handle());
+
+        let unit = instance.time_unit()?;
+        let raw_time = get_time(instance.handle.as_ptr(), TimeType::ScaledRealTime);
+        match result::last_vpi_error() {
+            Some(error) => Err(error),
+            None        => Ok(ScaledTime { value: raw_time.real, unit })
+        }
+    }
+}
+
+impl ObjectHandle {
+    ///The time unit delays in the object's module are in (vpiTimeUnit)
+    pub fn time_unit(&self) -> Result<TimeUnit> {
+        get_time_unit(self.handle.as_ptr(), Property::TimeUnit)
+    }
+
+    ///The precision delays in the object's module are rounded to (vpiTimePrecision)
+    pub fn time_precision(&self) -> Result<TimeUnit> {
+        get_time_unit(self.handle.as_ptr(), Property::TimePrecision)
+    }
+}
+
+/* ------------------------------------------------------------------------------------------------
+ * Trait Implementations
+ * --------------------------------------------------------------------------------------------- */
+
+impl Add for SimTime {
+    type Output = SimTime;
+
+    fn add(self, rhs: SimTime) -> SimTime {
+        SimTime(self.0 + rhs.0)
+    }
+}
+
+impl AddAssign for SimTime {
+    fn add_assign(&mut self, rhs: SimTime) {
+        self.0 += rhs.0;
+    }
+}
+
+impl Sub for SimTime {
+    type Output = SimTime;
+
+    fn sub(self, rhs: SimTime) -> SimTime {
+        SimTime(self.0 - rhs.0)
+    }
+}
+
+impl SubAssign for SimTime {
+    fn sub_assign(&mut self, rhs: SimTime) {
+        self.0 -= rhs.0;
+    }
+}
+
+impl From<u64> for SimTime {
+    fn from(ticks: u64) -> SimTime {
+        SimTime(ticks)
+    }
+}
+
+impl From<SimTime> for u64 {
+    fn from(time: SimTime) -> u64 {
+        time.0
+    }
+}
+
+impl From<SimTime> for sv_bindings::t_vpi_time {
+    fn from(time: SimTime) -> sv_bindings::t_vpi_time {
+        sv_bindings::t_vpi_time {
+            type_: TimeType::SimTime as i32,
+            high: time.high(),
+            low: time.low(),
+            real: 0.0
+        }
+    }
+}
+
+impl TryFrom<sv_bindings::t_vpi_time> for SimTime {
+    type Error = Box<Error>;
+
+    ///Fails if the time isn't a vpiSimTime
+    fn try_from(raw_time: sv_bindings::t_vpi_time) -> Result<SimTime> {
+        if raw_time.type_ == sv_bindings::vpiSimTime {
+            Ok(SimTime::from_high_low(raw_time.high, raw_time.low))
+        } else {
+            Err(Box::new(Error::WrongTimeType(raw_time.type_)))
+        }
+    }
+}
+
+impl From<SimTime> for callbacks::Time {
+    fn from(time: SimTime) -> callbacks::Time {
+        callbacks::Time::SimTime{high: time.high(), low: time.low()}
+    }
+}
+
+impl TryFrom<callbacks::Time> for SimTime {
+    type Error = Box<Error>;
+
+    ///Fails if the time isn't a `Time::SimTime`
+    fn try_from(time: callbacks::Time) -> Result<SimTime> {
+        sv_bindings::t_vpi_time::from(time).try_into()
+    }
+}
+
+///In the base unit, like "1250 ns" for 125 10ns units
+impl Display for ScaledTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.value * self.unit.magnitude() as f64, self.unit.suffix())
+    }
+}
+
+///Like a `` `timescale `` directive (ex. "10ns/1ps")
+impl Display for Timescale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.unit, self.precision)
+    }
+}
+
+impl TryFrom<i32> for TimeUnit {
+    type Error = Box<Error>;
+
+    fn try_from(exponent: i32) -> Result<TimeUnit> {
+        TimeUnit::from_exponent(exponent).ok_or_else(|| Box::new(Error::InvalidTimeUnit(exponent)))
+    }
+}
+
+///Like in a `` `timescale `` directive (ex. "10ns")
+impl Display for TimeUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.magnitude(), self.suffix())
+    }
+}
+
+/* ------------------------------------------------------------------------------------------------
+ * Functions
+ * --------------------------------------------------------------------------------------------- */
+
+///The current simulation time in ticks of the simulation's precision (using `vpi_get_time()`)
+pub fn sim_time() -> SimTime {
+    let raw_time = get_time(std::ptr::null_mut(), TimeType::SimTime);
+    SimTime::from_high_low(raw_time.high, raw_time.low)
+}
+
+///The length of one tick of simulation time: the finest precision of any module in the design
+pub fn simulation_precision() -> Result<TimeUnit> {
+    //A null handle asks for the simulation's precision
+    get_time_unit(std::ptr::null_mut(), Property::TimePrecision)
+}
+
+///Gets a time unit property of the object, or of the simulation if the object is null
+///
+///Unlike ObjectHandle::get() this doesn't treat vpiUndefined as an error, since -1 is also the
+///exponent of 100ms. Only vpi_chk_error() can tell the two apart.
+fn get_time_unit(object: sv_bindings::vpiHandle, property: Property) -> Result<TimeUnit> {
+    panic_if_in_startup_routine!();
+    panic_if_not_main_thread!();
+
+    //SAFETY: We're calling vpi_get() from the main thread after startup routines have finished, and
+    //the object is either null or a valid handle
+    let raw_exponent = unsafe { sv_bindings::vpi_get(property as i32, object) };
+
+    match result::last_vpi_error() {
+        Some(error) => Err(error),
+        None        => raw_exponent.try_into()
+    }
+}
+
+///Gets the current time in the given format, for the object's time unit if it isn't null
+fn get_time(object: sv_bindings::vpiHandle, time_type: TimeType) -> sv_bindings::t_vpi_time {
+    panic_if_in_startup_routine!();
+    panic_if_not_main_thread!();
+
+    let mut raw_time = sv_bindings::t_vpi_time {
+        type_: time_type as i32,
+        high: 0,
+        low: 0,
+        real: 0.0
+    };
+
+    //SAFETY: We're calling vpi_get_time() from the main thread after startup routines have
+    //finished, the object is either null or a valid handle, and raw_time is a valid struct for the
+    //simulator to fill in
+    unsafe { sv_bindings::vpi_get_time(object, &mut raw_time) };
+
+    raw_time
+}
+
+/* ------------------------------------------------------------------------------------------------
+ * Tests
+ * --------------------------------------------------------------------------------------------- */
+
+//TODO
+
+/* ------------------------------------------------------------------------------------------------
+ * Benchmarks
+ * --------------------------------------------------------------------------------------------- */
+
+//TODO
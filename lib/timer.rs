@@ -0,0 +1,176 @@
+/*
+ * File:    timer.rs
+ * Brief:   Calling closures after a delay, at a given time, or periodically.
+ *
+ * Copyright (C) 2023 John Jekel
+ * See the LICENSE file at the root of the project for licensing info.
+ *
+ * Periodic timers are a chain of one-shot cbAfterDelay callbacks, each registering the next right
+ * after calling the closure. The closure and the callback currently waiting to fire are shared with
+ * the Timer through an Rc so that it can stop the chain at any point.
+ *
+*/
+
+/*!
+ * Calling closures after a delay, at a given time, or periodically.
+ *
+ * - [`after()`] calls a closure once, some delay from now (like `#delay` in SystemVerilog)
+ * - [`at()`] calls a closure once, at the start of an absolute simulation time
+ * - [`every()`] calls a closure over and over with a fixed period, until the returned [`Timer`] is
+ *   stopped
+ *
+ * Delays and periods are [`callbacks::Time`]s, so a [`SimTime`] works too:
+ *
+ * ```ignore
+ * let heartbeat = timer::every(SimTime(1000), |event| sim_println!("Still alive at {:?}", event.time()))?;
+ * timer::after(SimTime(1_000_000), move |_| heartbeat.stop().expect("Failed to stop the heartbeat"))?;
+ * ```
+ *
+*/
+
+/* ------------------------------------------------------------------------------------------------
+ * Uses
+ * --------------------------------------------------------------------------------------------- */
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use crate::callbacks::{self, CallbackBuilder, CallbackEvent, CallbackHandle, CallbackReason};
+use crate::result::{Error, Result};
+use crate::time::SimTime;
+
+/* ------------------------------------------------------------------------------------------------
+ * Types
+ * --------------------------------------------------------------------------------------------- */
+
+///A periodic timer started with [`every()`]
+///
+///Dropping the timer does not stop it.
+#[derive(Clone)]
+pub struct Timer {
+    state: Rc<TimerState>
+}
+
+type TimerClosure = Box<dyn FnMut(&CallbackEvent)>;
+
+struct TimerState {
+    period: callbacks::Time,
+    func: RefCell<TimerClosure>,
+    //The callback for the next tick, which is replaced every time the timer fires
+    next_tick: RefCell<Option<CallbackHandle>>,
+    stopped: Cell<bool>
+}
+
+/* ------------------------------------------------------------------------------------------------
+ * Associated Functions and Methods
+ * --------------------------------------------------------------------------------------------- */
+
+impl Timer {
+    ///Stops the timer so the closure is never called again
+    ///
+    ///This may be called from within the timer's own closure.
+    pub fn stop(&self) -> Result<()> {
+        self.state.stopped.set(true);
+        match self.state.next_tick.borrow_mut().take() {
+            Some(next_tick) => next_tick.remove(),
+            None            => Ok(())
+        }
+    }
+
+    ///False once the timer was stopped (or failed to schedule its next tick)
+    pub fn is_running(&self) -> bool {
+        !self.state.stopped.get()
+    }
+
+    ///Registers the callback for the next tick
+    fn schedule(state: &Rc<TimerState>) -> Result<()> {
+        let callback_state = state.clone();
+        let next_tick = CallbackBuilder::new()
+            .reason(CallbackReason::AfterDelay)
+            .time(state.period)
+            .call(move |event| {
+                if callback_state.stopped.get() {
+                    return;
+                }
+
+                (callback_state.func.borrow_mut())(event);
+
+                //The closure may have stopped the timer. If we can't schedule the next tick
+                //there's nobody to report the error to, so the timer just stops.
+                if !callback_state.stopped.get() && Timer::schedule(&callback_state).is_err() {
+                    callback_state.stopped.set(true);
+                }
+            })
+            .register()?;
+
+        *state.next_tick.borrow_mut() = Some(next_tick);
+        Ok(())
+    }
+}
+
+/* ------------------------------------------------------------------------------------------------
+ * Functions
+ * --------------------------------------------------------------------------------------------- */
+
+///Calls the closure once, after the delay (using cbAfterDelay)
+pub fn after(delay: impl Into<callbacks::Time>, func: impl FnOnce(&CallbackEvent) + 'static) -> Result<CallbackHandle> {
+    once(CallbackReason::AfterDelay, delay.into(), func)
+}
+
+///Calls the closure once, at the start of the given simulation time (using cbAtStartOfSimTime)
+///
+///The time must not be in the past.
+pub fn at(time: SimTime, func: impl FnOnce(&CallbackEvent) + 'static) -> Result<CallbackHandle> {
+    once(CallbackReason::AtStartOfSimTime, time.into(), func)
+}
+
+///Calls the closure every period, starting one period from now, until the timer is stopped
+///
+///The period must be positive, since otherwise the timer would keep firing without simulation time
+///ever advancing.
+pub fn every(period: impl Into<callbacks::Time>, func: impl FnMut(&CallbackEvent) + 'static) -> Result<Timer> {
+    let period = period.into();
+    let advances = match period {
+        callbacks::Time::SimTime { high, low }  => (high, low) != (0, 0),
+        callbacks::Time::ScaledRealTime(real)   => real > 0.0,//Also rejects NaN
+        callbacks::Time::SuppressTime           => false
+    };
+    if !advances {
+        return Err(Box::new(Error::NonAdvancingPeriod(period)));
+    }
+
+    let state = Rc::new(TimerState {
+        period,
+        func: RefCell::new(Box::new(func)),
+        next_tick: RefCell::new(None),
+        stopped: Cell::new(false)
+    });
+
+    Timer::schedule(&state)?;
+    Ok(Timer { state })
+}
+
+fn once(reason: CallbackReason, time: callbacks::Time, func: impl FnOnce(&CallbackEvent) + 'static) -> Result<CallbackHandle> {
+    let mut func = Some(func);
+    CallbackBuilder::new()
+        .reason(reason)
+        .time(time)
+        .call(move |event| {
+            if let Some(func) = func.take() {
+                func(event);
+            }
+        })
+        .register()
+}
+
+/* ------------------------------------------------------------------------------------------------
+ * Tests
+ * --------------------------------------------------------------------------------------------- */
+
+//TODO
+
+/* ------------------------------------------------------------------------------------------------
+ * Benchmarks
+ * --------------------------------------------------------------------------------------------- */
+
+//TODO
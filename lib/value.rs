@@ -0,0 +1,735 @@
+/*
+ * File:    value.rs
+ * Brief:   Reading and writing the values of objects (using vpi_get_value() and vpi_put_value()).
+ *
+ * Copyright (C) 2023 John Jekel
+ * See the LICENSE file at the root of the project for licensing info.
+ *
+ * Every format the standard defines for s_vpi_value is supported. Anything the simulator hands back
+ * by pointer (strings, vectors, strengths and times) lives in a buffer that the next call may
+ * overwrite, so it is always copied out before get_value() returns. Going the other way,
+ * put_value() keeps the buffers it hands to the simulator alive for the duration of the call.
+ *
+*/
+
+/*!
+ * Reading and writing the values of objects.
+ *
+ * [`ObjectHandle::get_value()`] is generic over the format to read the value in. Each format maps to
+ * a Rust type:
+ *
+ * | Format           | Type                                |
+ * |------------------|-------------------------------------|
+ * | `vpiBinStrVal`   | [`BinStr`]                          |
+ * | `vpiOctStrVal`   | [`OctStr`]                          |
+ * | `vpiDecStrVal`   | [`DecStr`]                          |
+ * | `vpiHexStrVal`   | [`HexStr`]                          |
+ * | `vpiScalarVal`   | [`Logic`]                           |
+ * | `vpiIntVal`      | [`i32`]                             |
+ * | `vpiRealVal`     | [`f64`]                             |
+ * | `vpiStringVal`   | [`String`]                          |
+ * | `vpiVectorVal`   | [`LogicVec`]                        |
+ * | `vpiStrengthVal` | [`Vec<StrengthValue>`](StrengthValue) |
+ * | `vpiTimeVal`     | [`Time`]                            |
+ * | `vpiObjTypeVal`  | [`Value`]                           |
+ *
+ * For example, `handle.get_value::<LogicVec>()` or `handle.get_value::<HexStr>()`.
+ *
+ * [`ObjectHandle::put_value()`] accepts the same types (plus `&str` and single [`StrengthValue`]s),
+ * and a [`PutMode`] that controls when and how the new value takes effect. For example,
+ * `handle.put_value(LogicVec::from_u64(5, 8), PutMode::Inertial(Time::SimTime{high: 0, low: 10}))`.
+ * Delayed writes return a [`ScheduledEvent`] that can cancel the write if it hasn't happened yet.
+ *
+*/
+
+/* ------------------------------------------------------------------------------------------------
+ * Uses
+ * --------------------------------------------------------------------------------------------- */
+
+use std::ffi::CString;
+use std::fmt;
+use std::fmt::Display;
+
+use crate::{ObjectHandle, Property};
+use crate::callbacks::Time;
+use crate::logic::{Logic, LogicVec};
+use crate::objects::ScheduledEvent;
+use crate::result::{self, Error, Result};
+use crate::startup::{panic_if_in_startup_routine, panic_if_not_main_thread};
+
+/* ------------------------------------------------------------------------------------------------
+ * Macros
+ * --------------------------------------------------------------------------------------------- */
+
+///Implements GetValue and PutValue for the formats that are strings
+macro_rules! string_formats {
+    ($($type:ty = $format:path,)*) => {
+        $(
+            impl GetValue for $type {
+                const FORMAT: i32 = $format;
+
+                unsafe fn from_raw_value(raw_value: &sv_bindings::t_vpi_value, _size: Option<usize>) -> Result<Self> {
+                    //SAFETY: The caller guarantees vpi_get_value() just filled in the str_ member
+                    Ok(Self::from(unsafe { copy_str(raw_value.value.str_, $format) }?))
+                }
+            }
+
+            impl PutValue for $type {
+                fn to_raw_value(&self, _size: Option<usize>) -> Result<RawValue> {
+                    RawValue::from_str($format, self.as_ref())
+                }
+            }
+        )*
+    };
+}
+
+/* ------------------------------------------------------------------------------------------------
+ * Types
+ * --------------------------------------------------------------------------------------------- */
+
+///How and when a value written with [`ObjectHandle::put_value()`] takes effect
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PutMode {
+    ///Immediately, without scheduling an event (vpiNoDelay)
+    NoDelay,
+    ///After the delay, cancelling any event already scheduled for the object (vpiInertialDelay)
+    Inertial(Time),
+    ///After the delay, keeping events already scheduled for later times (vpiTransportDelay)
+    Transport(Time),
+    ///After the delay, keeping every event already scheduled (vpiPureTransportDelay)
+    PureTransport(Time),
+    ///Forces the object to the value until it is released (vpiForceFlag)
+    Force,
+    ///Releases a previous force; the value is ignored (vpiReleaseFlag)
+    Release,
+    ///Cancels the event the object refers to, which must be a vpiSchedEvent (vpiCancelEvent)
+    CancelEvent
+}
+
+///An s_vpi_value along with the buffers it points to
+#[doc(hidden)]
+pub struct RawValue {
+    raw_value: sv_bindings::t_vpi_value,
+    _buffer: RawValueBuffer
+}
+
+//Whatever raw_value points to; moving these doesn't move the heap allocations they own
+#[allow(dead_code)]//The buffers are only held onto, never read
+enum RawValueBuffer {
+    None,
+    String(CString),
+    Vector(Vec<sv_bindings::t_vpi_vecval>),
+    Strength(Vec<sv_bindings::t_vpi_strengthval>),
+    Time(Box<sv_bindings::t_vpi_time>)
+}
+
+///The value as a string of binary digits (ex. "10xz")
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BinStr(pub String);
+
+///The value as a string of octal digits
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct OctStr(pub String);
+
+///The value as a string of decimal digits
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DecStr(pub String);
+
+///The value as a string of hexadecimal digits
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct HexStr(pub String);
+
+///A drive or charge strength, ordered from weakest to strongest
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(i32)]
+pub enum Strength {
+    HighZ = sv_bindings::vpiHiZ,
+    Small = sv_bindings::vpiSmallCharge,
+    Medium = sv_bindings::vpiMediumCharge,
+    Weak = sv_bindings::vpiWeakDrive,
+    Large = sv_bindings::vpiLargeCharge,
+    Pull = sv_bindings::vpiPullDrive,
+    Strong = sv_bindings::vpiStrongDrive,
+    Supply = sv_bindings::vpiSupplyDrive
+}
+
+///The logic value of a single bit along with the strengths it is driven with (s_vpi_strengthval)
+///
+///`strength0` is the strength of whatever is driving the bit towards 0 and `strength1` towards 1. A
+///bit at 0 or 1 only has the corresponding strength, while an X may have both when there is
+///contention. When the simulator reports an ambiguous range of strengths, the strongest is used.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct StrengthValue {
+    pub logic: Logic,
+    pub strength0: Strength,
+    pub strength1: Strength
+}
+
+///A value in whatever format the simulator thinks is most appropriate for the object
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum Value {
+    BinStr(BinStr),
+    OctStr(OctStr),
+    DecStr(DecStr),
+    HexStr(HexStr),
+    Scalar(Logic),
+    Int(i32),
+    Real(f64),
+    String(String),
+    Vector(LogicVec),
+    Strength(Vec<StrengthValue>),
+    Time(Time)
+}
+
+/* ------------------------------------------------------------------------------------------------
+ * Associated Functions and Methods
+ * --------------------------------------------------------------------------------------------- */
+
+impl ObjectHandle {
+    ///Gets the value of the object in the format corresponding to `T` (using `vpi_get_value()`)
+    pub fn get_value<T: GetValue>(&self) -> Result<T> {
+        panic_if_in_startup_routine!();
+        panic_if_not_main_thread!();
+
+        //Looked up first since any VPI call after vpi_get_value() could overwrite the value's buffers
+        let size = self.size().ok();
+
+        //The pointer member covers the whole union, so pointer formats stay null if the simulator
+        //doesn't fill them in
+        let mut raw_value = sv_bindings::t_vpi_value {
+            format: T::FORMAT,
+            value: sv_bindings::t_vpi_value__bindgen_ty_1 { str_: std::ptr::null_mut() }
+        };
+
+        //SAFETY: We're calling vpi_get_value() from the main thread after startup routines have
+        //finished, the handle is guaranteed to be valid and non-null, and raw_value is a valid
+        //struct for the simulator to fill in
+        unsafe { sv_bindings::vpi_get_value(self.handle.as_ptr(), &mut raw_value) };
+
+        //SAFETY: vpi_get_value() just filled in raw_value and we haven't made any other calls that
+        //could have overwritten the buffers it points to. Null pointers are caught if it failed.
+        let value = unsafe { T::from_raw_value(&raw_value, size) };
+
+        //Checked only after copying the value out, since vpi_chk_error() is a VPI call too
+        if let Some(error) = result::last_vpi_error() {
+            return Err(error);
+        }
+
+        value
+    }
+
+    ///Sets the value of the object (using `vpi_put_value()`)
+    ///
+    ///Vectors are zero-extended or truncated to the width of the object, like in a SystemVerilog
+    ///assignment. Strength values must have exactly one entry per bit of the object.
+    ///
+    ///Writes with a delay return the [`ScheduledEvent`] for the write, which can be used to cancel it
+    ///before it happens. Other modes return None. The event is returned whenever the simulator
+    ///schedules the write, even if it also reported an error; [`ScheduledEvent::is_scheduled()`] tells
+    ///whether the write is actually pending.
+    pub fn put_value(&self, value: impl PutValue, mode: PutMode) -> Result<Option<ScheduledEvent>> {
+        panic_if_in_startup_routine!();
+        panic_if_not_main_thread!();
+
+        let size = self.size().ok();
+        let mut raw_value = value.to_raw_value(size)?;
+        let (flags, delay) = match mode {
+            PutMode::NoDelay                => (sv_bindings::vpiNoDelay, None),
+            PutMode::Inertial(delay)        => (sv_bindings::vpiInertialDelay, Some(delay)),
+            PutMode::Transport(delay)       => (sv_bindings::vpiTransportDelay, Some(delay)),
+            PutMode::PureTransport(delay)   => (sv_bindings::vpiPureTransportDelay, Some(delay)),
+            PutMode::Force                  => (sv_bindings::vpiForceFlag, None),
+            PutMode::Release                => (sv_bindings::vpiReleaseFlag, None),
+            PutMode::CancelEvent            => (sv_bindings::vpiCancelEvent, None)
+        };
+        let mut raw_delay: Option<sv_bindings::t_vpi_time> = delay.map(|delay| delay.into());
+        let raw_delay_ptr = raw_delay.as_mut().map_or(std::ptr::null_mut(), |raw_delay| raw_delay as *mut _);
+
+        //Only ask for the event back when there will be one, otherwise we'd have nothing to release it
+        let flags = if delay.is_some() { flags | sv_bindings::vpiReturnEvent } else { flags };
+
+        //SAFETY: We're calling vpi_put_value() from the main thread after startup routines have
+        //finished, the handle is guaranteed to be valid and non-null, and raw_value (along with the
+        //buffers it points to) and raw_delay outlive the call
+        let raw_event = unsafe {
+            sv_bindings::vpi_put_value(self.handle.as_ptr(), &mut raw_value.raw_value, raw_delay_ptr, flags)
+        };
+
+        //If the simulator gave us an event the write is scheduled no matter what else it reported, so
+        //we return it rather than lose the only way to cancel the write
+        if let Some(event) = ObjectHandle::from_raw(raw_event) {
+            return Ok(Some(ScheduledEvent::from_returned_event(event)));
+        }
+
+        match result::last_vpi_error() {
+            Some(error) => Err(error),
+            None        => Ok(None)
+        }
+    }
+}
+
+impl RawValue {
+    fn new(format: i32, value: sv_bindings::t_vpi_value__bindgen_ty_1, buffer: RawValueBuffer) -> RawValue {
+        RawValue {
+            raw_value: sv_bindings::t_vpi_value { format, value },
+            _buffer: buffer
+        }
+    }
+
+    fn from_str(format: i32, string: &str) -> Result<RawValue> {
+        let cstring = CString::new(string).map_err(|e| Box::new(Error::Other(Box::new(e))))?;
+        //The simulator doesn't modify the string; it's only *mut because of the C API
+        let str_ = cstring.as_ptr() as *mut sv_bindings::PLI_BYTE8;
+        Ok(RawValue::new(format, sv_bindings::t_vpi_value__bindgen_ty_1 { str_ }, RawValueBuffer::String(cstring)))
+    }
+}
+
+impl Strength {
+    ///Picks the strongest strength out of the bitmask in s_vpi_strengthval
+    fn from_raw_mask(raw_mask: i32) -> Strength {
+        [
+            Strength::Supply, Strength::Strong, Strength::Pull, Strength::Large,
+            Strength::Weak, Strength::Medium, Strength::Small
+        ].into_iter().find(|&strength| raw_mask & strength as i32 != 0).unwrap_or(Strength::HighZ)
+    }
+
+    ///The two letter abbreviation used by `%v` in SystemVerilog (ex. "St" for strong)
+    pub fn abbreviation(self) -> &'static str {
+        match self {
+            Strength::Supply    => "Su",
+            Strength::Strong    => "St",
+            Strength::Pull      => "Pu",
+            Strength::Large     => "La",
+            Strength::Weak      => "We",
+            Strength::Medium    => "Me",
+            Strength::Small     => "Sm",
+            Strength::HighZ     => "Hi"
+        }
+    }
+}
+
+impl StrengthValue {
+    ///A bit driven to 0 or 1 (or X or Z) with a single strength
+    pub fn driven(logic: Logic, strength: Strength) -> StrengthValue {
+        match logic {
+            Logic::Zero => StrengthValue { logic, strength0: strength, strength1: Strength::HighZ },
+            Logic::One  => StrengthValue { logic, strength0: Strength::HighZ, strength1: strength },
+            Logic::X    => StrengthValue { logic, strength0: strength, strength1: strength },
+            Logic::Z    => StrengthValue { logic, strength0: Strength::HighZ, strength1: Strength::HighZ }
+        }
+    }
+
+    ///The strength the bit is driven to its logic value with (the stronger of the two for an X)
+    pub fn strength(&self) -> Strength {
+        match self.logic {
+            Logic::Zero => self.strength0,
+            Logic::One  => self.strength1,
+            _           => self.strength0.max(self.strength1)
+        }
+    }
+
+    ///True if the bit is an X because it is being driven towards both 0 and 1 at the same time
+    pub fn is_contention(&self) -> bool {
+        self.logic == Logic::X && self.strength0 != Strength::HighZ && self.strength1 != Strength::HighZ
+    }
+}
+
+/* ------------------------------------------------------------------------------------------------
+ * Traits And Default Implementations
+ * --------------------------------------------------------------------------------------------- */
+
+///A type that [`ObjectHandle::get_value()`] can read a value as
+pub trait GetValue: Sized {
+    ///The s_vpi_value format to ask the simulator for
+    const FORMAT: i32;
+
+    ///Copies the value out of what vpi_get_value() filled in
+    ///
+    ///`size` is the object's vpiSize, which has to be looked up beforehand since vpi_get() could
+    ///overwrite the buffers the value points to.
+    ///
+    ///# Safety
+    ///
+    ///`raw_value` must have just been filled in by vpi_get_value() for an object of `size` bits, with
+    ///no VPI calls since that could have overwritten the buffers it points to
+    #[doc(hidden)]
+    unsafe fn from_raw_value(raw_value: &sv_bindings::t_vpi_value, size: Option<usize>) -> Result<Self>;
+}
+
+///A type that [`ObjectHandle::put_value()`] can write
+pub trait PutValue {
+    ///Converts the value into an s_vpi_value, along with any buffers it needs to point to
+    ///
+    ///`size` is the vpiSize of the object being written. The simulator reads as much of a vector or
+    ///strength value as the object needs, no matter how long the buffer actually is.
+    #[doc(hidden)]
+    fn to_raw_value(&self, size: Option<usize>) -> Result<RawValue>;
+}
+
+/* ------------------------------------------------------------------------------------------------
+ * Trait Implementations
+ * --------------------------------------------------------------------------------------------- */
+
+string_formats!(
+    BinStr = sv_bindings::vpiBinStrVal,
+    OctStr = sv_bindings::vpiOctStrVal,
+    DecStr = sv_bindings::vpiDecStrVal,
+    HexStr = sv_bindings::vpiHexStrVal,
+    String = sv_bindings::vpiStringVal,
+);
+
+impl From<String> for BinStr {
+    fn from(string: String) -> BinStr {
+        BinStr(string)
+    }
+}
+
+impl From<String> for OctStr {
+    fn from(string: String) -> OctStr {
+        OctStr(string)
+    }
+}
+
+impl From<String> for DecStr {
+    fn from(string: String) -> DecStr {
+        DecStr(string)
+    }
+}
+
+impl From<String> for HexStr {
+    fn from(string: String) -> HexStr {
+        HexStr(string)
+    }
+}
+
+impl AsRef<str> for BinStr {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for OctStr {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for DecStr {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for HexStr {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl GetValue for Logic {
+    const FORMAT: i32 = sv_bindings::vpiScalarVal;
+
+    unsafe fn from_raw_value(raw_value: &sv_bindings::t_vpi_value, _size: Option<usize>) -> Result<Logic> {
+        //SAFETY: The caller guarantees vpi_get_value() just filled in the scalar member
+        Ok(logic_from_scalar(unsafe { raw_value.value.scalar }))
+    }
+}
+
+impl GetValue for i32 {
+    const FORMAT: i32 = sv_bindings::vpiIntVal;
+
+    unsafe fn from_raw_value(raw_value: &sv_bindings::t_vpi_value, _size: Option<usize>) -> Result<i32> {
+        //SAFETY: The caller guarantees vpi_get_value() just filled in the integer member
+        Ok(unsafe { raw_value.value.integer })
+    }
+}
+
+impl GetValue for f64 {
+    const FORMAT: i32 = sv_bindings::vpiRealVal;
+
+    unsafe fn from_raw_value(raw_value: &sv_bindings::t_vpi_value, _size: Option<usize>) -> Result<f64> {
+        //SAFETY: The caller guarantees vpi_get_value() just filled in the real member
+        Ok(unsafe { raw_value.value.real })
+    }
+}
+
+impl GetValue for LogicVec {
+    const FORMAT: i32 = sv_bindings::vpiVectorVal;
+
+    unsafe fn from_raw_value(raw_value: &sv_bindings::t_vpi_value, size: Option<usize>) -> Result<LogicVec> {
+        //SAFETY: The caller guarantees vpi_get_value() just filled in the vector member
+        let raw_vector = unsafe { raw_value.value.vector };
+        if raw_vector.is_null() {
+            return Err(Box::new(Error::UnsupportedValueFormat(sv_bindings::vpiVectorVal)));
+        }
+
+        let width = size.ok_or(Box::new(Error::UndefinedProperty(Property::Size)))?;
+
+        //SAFETY: The simulator provides one s_vpi_vecval for every 32 bits of the object
+        let words = unsafe { std::slice::from_raw_parts(raw_vector, width.div_ceil(32)) };
+        Ok(LogicVec::from_vecval(words, width))
+    }
+}
+
+impl GetValue for Vec<StrengthValue> {
+    const FORMAT: i32 = sv_bindings::vpiStrengthVal;
+
+    unsafe fn from_raw_value(raw_value: &sv_bindings::t_vpi_value, size: Option<usize>) -> Result<Vec<StrengthValue>> {
+        //SAFETY: The caller guarantees vpi_get_value() just filled in the strength member
+        let raw_strengths = unsafe { raw_value.value.strength };
+        if raw_strengths.is_null() {
+            return Err(Box::new(Error::UnsupportedValueFormat(sv_bindings::vpiStrengthVal)));
+        }
+
+        //Scalars may not have a size, but they always have exactly one bit
+        let width = size.unwrap_or(1);
+
+        //SAFETY: The simulator provides one s_vpi_strengthval for every bit of the object
+        let raw_strengths = unsafe { std::slice::from_raw_parts(raw_strengths, width) };
+        Ok(raw_strengths.iter().map(|&raw_strength| raw_strength.into()).collect())
+    }
+}
+
+impl GetValue for Time {
+    const FORMAT: i32 = sv_bindings::vpiTimeVal;
+
+    unsafe fn from_raw_value(raw_value: &sv_bindings::t_vpi_value, _size: Option<usize>) -> Result<Time> {
+        //SAFETY: The caller guarantees vpi_get_value() just filled in the time member
+        let raw_time = unsafe { raw_value.value.time };
+        if raw_time.is_null() {
+            return Err(Box::new(Error::UnsupportedValueFormat(sv_bindings::vpiTimeVal)));
+        }
+
+        //SAFETY: We just checked that the pointer is non-null, and the simulator owns what it points to
+        Ok(unsafe { *raw_time }.into())
+    }
+}
+
+impl GetValue for Value {
+    const FORMAT: i32 = sv_bindings::vpiObjTypeVal;
+
+    unsafe fn from_raw_value(raw_value: &sv_bindings::t_vpi_value, size: Option<usize>) -> Result<Value> {
+        //The simulator replaces vpiObjTypeVal with the format it actually chose
+        //SAFETY: The caller's guarantees carry over to each of the formats
+        unsafe {
+            Ok(match raw_value.format {
+                sv_bindings::vpiBinStrVal   => Value::BinStr(BinStr::from_raw_value(raw_value, size)?),
+                sv_bindings::vpiOctStrVal   => Value::OctStr(OctStr::from_raw_value(raw_value, size)?),
+                sv_bindings::vpiDecStrVal   => Value::DecStr(DecStr::from_raw_value(raw_value, size)?),
+                sv_bindings::vpiHexStrVal   => Value::HexStr(HexStr::from_raw_value(raw_value, size)?),
+                sv_bindings::vpiScalarVal   => Value::Scalar(Logic::from_raw_value(raw_value, size)?),
+                sv_bindings::vpiIntVal      => Value::Int(i32::from_raw_value(raw_value, size)?),
+                sv_bindings::vpiRealVal     => Value::Real(f64::from_raw_value(raw_value, size)?),
+                sv_bindings::vpiStringVal   => Value::String(String::from_raw_value(raw_value, size)?),
+                sv_bindings::vpiVectorVal   => Value::Vector(LogicVec::from_raw_value(raw_value, size)?),
+                sv_bindings::vpiStrengthVal => Value::Strength(Vec::<StrengthValue>::from_raw_value(raw_value, size)?),
+                sv_bindings::vpiTimeVal     => Value::Time(Time::from_raw_value(raw_value, size)?),
+                other_format                => return Err(Box::new(Error::UnsupportedValueFormat(other_format)))
+            })
+        }
+    }
+}
+
+impl PutValue for &str {
+    fn to_raw_value(&self, _size: Option<usize>) -> Result<RawValue> {
+        RawValue::from_str(sv_bindings::vpiStringVal, self)
+    }
+}
+
+impl PutValue for Logic {
+    fn to_raw_value(&self, _size: Option<usize>) -> Result<RawValue> {
+        let scalar = *self as i32;
+        Ok(RawValue::new(sv_bindings::vpiScalarVal, sv_bindings::t_vpi_value__bindgen_ty_1 { scalar }, RawValueBuffer::None))
+    }
+}
+
+impl PutValue for i32 {
+    fn to_raw_value(&self, _size: Option<usize>) -> Result<RawValue> {
+        let integer = *self;
+        Ok(RawValue::new(sv_bindings::vpiIntVal, sv_bindings::t_vpi_value__bindgen_ty_1 { integer }, RawValueBuffer::None))
+    }
+}
+
+impl PutValue for f64 {
+    fn to_raw_value(&self, _size: Option<usize>) -> Result<RawValue> {
+        let real = *self;
+        Ok(RawValue::new(sv_bindings::vpiRealVal, sv_bindings::t_vpi_value__bindgen_ty_1 { real }, RawValueBuffer::None))
+    }
+}
+
+impl PutValue for LogicVec {
+    fn to_raw_value(&self, size: Option<usize>) -> Result<RawValue> {
+        //Zero-extended or truncated to the object's width like a SystemVerilog assignment would be,
+        //so the simulator never reads past the end of the buffer
+        let width = size.ok_or(Box::new(Error::UndefinedProperty(Property::Size)))?;
+        let mut words = self.resized(width).to_vecval();
+        let vector = words.as_mut_ptr();
+        Ok(RawValue::new(sv_bindings::vpiVectorVal, sv_bindings::t_vpi_value__bindgen_ty_1 { vector }, RawValueBuffer::Vector(words)))
+    }
+}
+
+impl PutValue for [StrengthValue] {
+    fn to_raw_value(&self, size: Option<usize>) -> Result<RawValue> {
+        //The simulator reads one s_vpi_strengthval per bit of the object, so a shorter slice would be
+        //read past its end. Scalars may not have a size, but they always have exactly one bit.
+        let width = size.unwrap_or(1);
+        if self.len() != width {
+            return Err(Box::new(Error::WrongValueWidth { expected: width, actual: self.len() }));
+        }
+
+        let mut raw_strengths: Vec<sv_bindings::t_vpi_strengthval> = self.iter().map(|&value| value.into()).collect();
+        let strength = raw_strengths.as_mut_ptr();
+        Ok(RawValue::new(sv_bindings::vpiStrengthVal, sv_bindings::t_vpi_value__bindgen_ty_1 { strength }, RawValueBuffer::Strength(raw_strengths)))
+    }
+}
+
+impl PutValue for Vec<StrengthValue> {
+    fn to_raw_value(&self, size: Option<usize>) -> Result<RawValue> {
+        self.as_slice().to_raw_value(size)
+    }
+}
+
+impl PutValue for StrengthValue {
+    fn to_raw_value(&self, size: Option<usize>) -> Result<RawValue> {
+        std::slice::from_ref(self).to_raw_value(size)
+    }
+}
+
+impl From<sv_bindings::t_vpi_strengthval> for StrengthValue {
+    fn from(raw_strength: sv_bindings::t_vpi_strengthval) -> StrengthValue {
+        StrengthValue {
+            logic: logic_from_scalar(raw_strength.logic),
+            strength0: Strength::from_raw_mask(raw_strength.s0),
+            strength1: Strength::from_raw_mask(raw_strength.s1)
+        }
+    }
+}
+
+impl From<StrengthValue> for sv_bindings::t_vpi_strengthval {
+    fn from(value: StrengthValue) -> sv_bindings::t_vpi_strengthval {
+        sv_bindings::t_vpi_strengthval {
+            logic: value.logic as i32,
+            s0: value.strength0 as i32,
+            s1: value.strength1 as i32
+        }
+    }
+}
+
+///Like `%v` in SystemVerilog (ex. "St1", "PuX", "HiZ")
+impl Display for StrengthValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.logic {
+            Logic::Z    => write!(f, "HiZ"),
+            _           => write!(f, "{}{}", self.strength().abbreviation(), self.logic.to_string().to_uppercase())
+        }
+    }
+}
+
+impl PutValue for Time {
+    fn to_raw_value(&self, _size: Option<usize>) -> Result<RawValue> {
+        let mut raw_time = Box::new(sv_bindings::t_vpi_time::from(*self));
+        let time = raw_time.as_mut() as *mut _;
+        Ok(RawValue::new(sv_bindings::vpiTimeVal, sv_bindings::t_vpi_value__bindgen_ty_1 { time }, RawValueBuffer::Time(raw_time)))
+    }
+}
+
+impl PutValue for Value {
+    fn to_raw_value(&self, size: Option<usize>) -> Result<RawValue> {
+        match self {
+            Value::BinStr(bin_str)  => bin_str.to_raw_value(size),
+            Value::OctStr(oct_str)  => oct_str.to_raw_value(size),
+            Value::DecStr(dec_str)  => dec_str.to_raw_value(size),
+            Value::HexStr(hex_str)  => hex_str.to_raw_value(size),
+            Value::Scalar(logic)    => logic.to_raw_value(size),
+            Value::Int(integer)     => integer.to_raw_value(size),
+            Value::Real(real)       => real.to_raw_value(size),
+            Value::String(string)   => string.to_raw_value(size),
+            Value::Vector(vector)   => vector.to_raw_value(size),
+            Value::Strength(values) => values.to_raw_value(size),
+            Value::Time(time)       => time.to_raw_value(size)
+        }
+    }
+}
+
+impl<T: PutValue + ?Sized> PutValue for &T {
+    fn to_raw_value(&self, size: Option<usize>) -> Result<RawValue> {
+        (**self).to_raw_value(size)
+    }
+}
+
+/* ------------------------------------------------------------------------------------------------
+ * Functions
+ * --------------------------------------------------------------------------------------------- */
+
+///Converts the value of a vpiScalarVal; vpiH and vpiL become 1 and 0 and vpiDontCare becomes X
+fn logic_from_scalar(raw_scalar: i32) -> Logic {
+    match raw_scalar {
+        sv_bindings::vpi0 | sv_bindings::vpiL   => Logic::Zero,
+        sv_bindings::vpi1 | sv_bindings::vpiH   => Logic::One,
+        sv_bindings::vpiZ                       => Logic::Z,
+        _                                       => Logic::X
+    }
+}
+
+///Copies a string out of the simulator's buffer
+///
+///# Safety
+///
+///`raw_str` must be null or point to a valid null-terminated string
+unsafe fn copy_str(raw_str: *const sv_bindings::PLI_BYTE8, format: i32) -> Result<String> {
+    if raw_str.is_null() {
+        return Err(Box::new(Error::UnsupportedValueFormat(format)));
+    }
+
+    //SAFETY: The caller guarantees the string is valid and null-terminated
+    Ok(unsafe { std::ffi::CStr::from_ptr(raw_str) }.to_string_lossy().into_owned())
+}
+
+/* ------------------------------------------------------------------------------------------------
+ * Tests
+ * --------------------------------------------------------------------------------------------- */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vecval_words(raw_value: &RawValue) -> Vec<(u32, u32)> {
+        match &raw_value._buffer {
+            RawValueBuffer::Vector(words) => words.iter().map(|word| (word.aval, word.bval)).collect(),
+            _ => panic!("A vector value should be backed by a vector buffer")
+        }
+    }
+
+    #[test]
+    fn narrower_vector_is_zero_extended_to_object_width() {
+        let raw_value = LogicVec::from_u64(5, 8).to_raw_value(Some(40)).unwrap();
+        assert_eq!(vecval_words(&raw_value), vec![(5, 0), (0, 0)]);
+    }
+
+    #[test]
+    fn wider_vector_is_truncated_to_object_width() {
+        let raw_value = LogicVec::from_u64(0x1_0000_0105, 40).to_raw_value(Some(8)).unwrap();
+        assert_eq!(vecval_words(&raw_value), vec![(5, 0)]);
+    }
+
+    #[test]
+    fn vector_needs_object_width() {
+        assert!(LogicVec::from_u64(5, 8).to_raw_value(None).is_err());
+    }
+
+    #[test]
+    fn strength_values_must_match_object_width() {
+        let value = StrengthValue::driven(Logic::One, Strength::Strong);
+        assert!(value.to_raw_value(Some(4)).is_err());
+        assert!(vec![value; 3].to_raw_value(Some(4)).is_err());
+        assert!(vec![value; 4].to_raw_value(Some(4)).is_ok());
+        assert!(value.to_raw_value(None).is_ok());
+    }
+}
+
+/* ------------------------------------------------------------------------------------------------
+ * Benchmarks
+ * --------------------------------------------------------------------------------------------- */
+
+//TODO